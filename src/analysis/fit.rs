@@ -0,0 +1,195 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+use crate::wafer::{MAXIMUM_CLUSTER_PARAMETER, MINIMUM_CLUSTER_PARAMETER, YieldModel};
+
+const DEFECT_RATE_RANGE: (f64, f64) = (1e-4, 1e4);
+const ITERATIONS: usize = 80;
+const EPSILON: f64 = 1e-9;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Sample {
+	pub name: String,
+	pub area: f32,
+	pub tested: usize,
+	pub good: usize,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Fit {
+	pub model: YieldModel,
+	pub defect_rate: f32,
+	pub cluster: f32,
+	pub log_likelihood: f64,
+	pub aic: f64,
+	pub chi_squared: f64,
+	pub rmse: f32,
+}
+
+impl Sample {
+	pub fn measured_yield(&self) -> f32 {
+		self.good as f32 / self.tested as f32
+	}
+}
+
+pub fn fit(model: YieldModel, samples: &[Sample], cluster: f32) -> Option<Fit> {
+	let samples: Vec<&Sample> = samples.iter().filter(|sample| sample.tested > 0).collect();
+	if samples.is_empty() {
+		return None;
+	}
+
+	let mut areas: Vec<f32> = samples.iter().map(|sample| sample.area).collect();
+	areas.sort_by(f32::total_cmp);
+	areas.dedup();
+	let fit_cluster = model == YieldModel::NegativeBinomial && areas.len() > 1;
+
+	let defect_rate = |cluster: f32| {
+		let log_likelihood = |log_rate: f64| log_likelihood(model, &samples, log_rate.exp() as f32, cluster);
+		maximise(log_likelihood, (DEFECT_RATE_RANGE.0.ln(), DEFECT_RATE_RANGE.1.ln())).exp() as f32
+	};
+	let cluster = if fit_cluster {
+		let log_likelihood = |log_cluster: f64| {
+			let cluster = log_cluster.exp() as f32;
+			log_likelihood(model, &samples, defect_rate(cluster), cluster)
+		};
+		let range = (
+			(MINIMUM_CLUSTER_PARAMETER as f64).ln(),
+			(MAXIMUM_CLUSTER_PARAMETER as f64).ln(),
+		);
+		maximise(log_likelihood, range).exp() as f32
+	} else {
+		cluster
+	};
+	let defect_rate = defect_rate(cluster);
+
+	let log_likelihood = log_likelihood(model, &samples, defect_rate, cluster);
+	let parameters = if fit_cluster { 2.0 } else { 1.0 };
+	let mut chi_squared = 0.0;
+	let mut squared_error = 0.0;
+	for sample in &samples {
+		let predicted = predicted_yield(model, sample.area, defect_rate, cluster) as f64;
+		let expected = sample.tested as f64 * predicted;
+		let variance = (expected * (1.0 - predicted)).max(EPSILON);
+		chi_squared += (sample.good as f64 - expected).powi(2) / variance;
+		squared_error += (sample.measured_yield() as f64 - predicted).powi(2);
+	}
+
+	Some(Fit {
+		model,
+		defect_rate,
+		cluster,
+		log_likelihood,
+		aic: 2.0 * parameters - 2.0 * log_likelihood,
+		chi_squared,
+		rmse: (squared_error / samples.len() as f64).sqrt() as f32,
+	})
+}
+
+pub fn fit_all(samples: &[Sample], cluster: f32) -> Vec<Fit> {
+	let mut fits: Vec<Fit> = YieldModel::ALL
+		.iter()
+		.filter_map(|&model| fit(model, samples, cluster))
+		.collect();
+	fits.sort_by(|a, b| a.aic.total_cmp(&b.aic));
+	fits
+}
+
+fn predicted_yield(model: YieldModel, area: f32, defect_rate: f32, cluster: f32) -> f32 {
+	model.defect_yield(area * defect_rate / 100.0, cluster)
+}
+
+fn log_likelihood(model: YieldModel, samples: &[&Sample], defect_rate: f32, cluster: f32) -> f64 {
+	samples
+		.iter()
+		.map(|sample| {
+			let predicted =
+				(predicted_yield(model, sample.area, defect_rate, cluster) as f64).clamp(EPSILON, 1.0 - EPSILON);
+			let bad = (sample.tested - sample.good) as f64;
+			sample.good as f64 * predicted.ln() + bad * (1.0 - predicted).ln()
+		})
+		.sum()
+}
+
+fn maximise(f: impl Fn(f64) -> f64, range: (f64, f64)) -> f64 {
+	let ratio = (5.0f64.sqrt() - 1.0) / 2.0;
+	let (mut low, mut high) = range;
+	let mut a = high - ratio * (high - low);
+	let mut b = low + ratio * (high - low);
+	let (mut fa, mut fb) = (f(a), f(b));
+	for _ in 0..ITERATIONS {
+		if fa > fb {
+			high = b;
+			b = a;
+			fb = fa;
+			a = high - ratio * (high - low);
+			fa = f(a);
+		} else {
+			low = a;
+			a = b;
+			fa = fb;
+			b = low + ratio * (high - low);
+			fb = f(b);
+		}
+	}
+	(low + high) / 2.0
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn samples(model: YieldModel, defect_rate: f32, cluster: f32) -> Vec<Sample> {
+		[25.0, 50.0, 100.0, 200.0]
+			.into_iter()
+			.map(|area| {
+				let tested = 10000;
+				let predicted = predicted_yield(model, area, defect_rate, cluster);
+				Sample {
+					name: format!("{} mm²", area),
+					area,
+					tested,
+					good: (tested as f32 * predicted).round() as usize,
+				}
+			})
+			.collect()
+	}
+
+	#[test]
+	fn recovers_poisson_defect_rate() {
+		let fit = fit(YieldModel::Poisson, &samples(YieldModel::Poisson, 0.5, 2.0), 2.0).unwrap();
+		assert!((fit.defect_rate - 0.5).abs() < 0.005, "{}", fit.defect_rate);
+		assert_eq!(fit.cluster, 2.0);
+		assert!(fit.rmse < 1e-3);
+		assert!(fit.chi_squared < 1.0);
+	}
+
+	#[test]
+	fn recovers_negative_binomial_cluster() {
+		let samples = samples(YieldModel::NegativeBinomial, 0.8, 1.5);
+		let fit = fit(YieldModel::NegativeBinomial, &samples, 10.0).unwrap();
+		assert!((fit.defect_rate - 0.8).abs() < 0.02, "{}", fit.defect_rate);
+		assert!((fit.cluster - 1.5).abs() < 0.1, "{}", fit.cluster);
+	}
+
+	#[test]
+	fn ranks_generating_model_first() {
+		let fits = fit_all(&samples(YieldModel::Seeds, 1.0, 2.0), 2.0);
+		assert_eq!(fits.len(), YieldModel::ALL.len());
+		assert_eq!(fits[0].model, YieldModel::Seeds);
+	}
+
+	#[test]
+	fn ignores_untested_samples() {
+		let untested = Sample {
+			name: String::from("Untested"),
+			area: 50.0,
+			tested: 0,
+			good: 0,
+		};
+		assert_eq!(fit(YieldModel::Murphy, &[untested], 2.0), None);
+		assert_eq!(fit(YieldModel::Murphy, &[], 2.0), None);
+	}
+}
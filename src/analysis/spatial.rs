@@ -0,0 +1,300 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+use crate::die::DieType;
+use crate::util::Coordinate;
+use crate::wafer::{Wafer, WaferMap};
+
+const SIGNIFICANCE: f64 = 1.96;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct RegionYield {
+	pub tested: usize,
+	pub good: usize,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct JoinCount {
+	pub joins: usize,
+	pub observed: usize,
+	pub expected: f64,
+	pub z_score: f64,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct SpatialStatistics {
+	pub overall: RegionYield,
+	pub cluster_index: f32,
+	pub clusters: usize,
+	pub largest_cluster: usize,
+	pub longest_run: usize,
+	pub center: RegionYield,
+	pub edge: RegionYield,
+	pub quadrants: [RegionYield; 4],
+	pub join_count: JoinCount,
+}
+
+impl RegionYield {
+	fn add(&mut self, good: bool) {
+		self.tested += 1;
+		self.good += good as usize;
+	}
+
+	pub fn die_yield(&self) -> f32 {
+		self.good as f32 / self.tested as f32
+	}
+}
+
+impl JoinCount {
+	pub fn is_clustered(&self) -> bool {
+		self.z_score > SIGNIFICANCE
+	}
+}
+
+pub fn tested_sites(map: &WaferMap) -> Vec<Vec<Option<bool>>> {
+	let mut tested: Vec<Vec<Option<bool>>> = map
+		.sites
+		.iter()
+		.map(|column| {
+			column
+				.iter()
+				.map(|site| (map.measured.is_empty() && site.die_type == DieType::Complete).then(|| site.is_good()))
+				.collect()
+		})
+		.collect();
+	for measurement in &map.measured {
+		tested[measurement.column][measurement.row] = Some(measurement.pass);
+	}
+	tested
+}
+
+pub fn site_center(wafer: &Wafer, map: &WaferMap, column: usize, row: usize) -> Coordinate {
+	let coord = map.sites[column][row].coord;
+	Coordinate {
+		x: coord.x + wafer.die.width() / 2.0 - wafer.shape.max_width() / 2.0,
+		y: wafer.shape.max_height() / 2.0 - coord.y - wafer.die.height() / 2.0,
+	}
+}
+
+pub fn spatial_statistics(wafer: &Wafer, map: &WaferMap) -> SpatialStatistics {
+	let tested = tested_sites(map);
+	let bad = |x: usize, y: usize| tested[x][y] == Some(false);
+	let neighbours = |x: usize, y: usize| {
+		let mut neighbours = Vec::with_capacity(4);
+		if x + 1 < tested.len() && tested[x + 1][y].is_some() {
+			neighbours.push((x + 1, y));
+		}
+		if y + 1 < tested[x].len() && tested[x][y + 1].is_some() {
+			neighbours.push((x, y + 1));
+		}
+		if x > 0 && tested[x - 1][y].is_some() {
+			neighbours.push((x - 1, y));
+		}
+		if y > 0 && tested[x][y - 1].is_some() {
+			neighbours.push((x, y - 1));
+		}
+		neighbours
+	};
+	let sites: Vec<(usize, usize)> = tested
+		.iter()
+		.enumerate()
+		.flat_map(|(x, column)| column.iter().enumerate().map(move |(y, site)| (x, y, site)))
+		.filter(|(_, _, site)| site.is_some())
+		.map(|(x, y, _)| (x, y))
+		.collect();
+
+	let radius = wafer.shape.max_width().min(wafer.shape.max_height()) / 2.0;
+	let mut overall = RegionYield::default();
+	let mut center = RegionYield::default();
+	let mut edge = RegionYield::default();
+	let mut quadrants = [RegionYield::default(); 4];
+	for &(x, y) in &sites {
+		let good = !bad(x, y);
+		overall.add(good);
+
+		let position = site_center(wafer, map, x, y);
+		if position.within_radius(&Coordinate { x: 0.0, y: 0.0 }, radius / 2.0f32.sqrt()) {
+			center.add(good);
+		} else {
+			edge.add(good);
+		}
+		let quadrant = match (position.x >= 0.0, position.y >= 0.0) {
+			(true, true) => 0,
+			(false, true) => 1,
+			(false, false) => 2,
+			(true, false) => 3,
+		};
+		quadrants[quadrant].add(good);
+	}
+
+	let mut joins = 0;
+	let mut bad_joins = 0;
+	let mut shared = 0;
+	for &(x, y) in &sites {
+		let neighbours = neighbours(x, y);
+		joins += neighbours.len();
+		shared += neighbours.len() * neighbours.len().saturating_sub(1) / 2;
+		if bad(x, y) {
+			bad_joins += neighbours.iter().filter(|&&(x, y)| bad(x, y)).count();
+		}
+	}
+	let (joins, bad_joins) = (joins / 2, bad_joins / 2);
+	let join_count = join_count(joins, shared, bad_joins, overall.tested, overall.tested - overall.good);
+
+	let bad_dies = overall.tested - overall.good;
+	let mean_neighbours = 2.0 * joins as f32 / overall.tested.max(1) as f32;
+	let expected_bad_neighbours =
+		mean_neighbours * bad_dies.saturating_sub(1) as f32 / (overall.tested.max(2) - 1) as f32;
+	let cluster_index = if bad_dies > 0 && expected_bad_neighbours > 0.0 {
+		(2.0 * bad_joins as f32 / bad_dies as f32) / expected_bad_neighbours
+	} else {
+		1.0
+	};
+
+	let mut visited = vec![vec![false; tested.first().map_or(0, Vec::len)]; tested.len()];
+	let mut clusters = 0;
+	let mut largest_cluster = 0;
+	for &(x, y) in &sites {
+		if !bad(x, y) || visited[x][y] {
+			continue;
+		}
+		clusters += 1;
+		let mut size = 0;
+		let mut stack = vec![(x, y)];
+		visited[x][y] = true;
+		while let Some((x, y)) = stack.pop() {
+			size += 1;
+			for (x, y) in neighbours(x, y) {
+				if bad(x, y) && !visited[x][y] {
+					visited[x][y] = true;
+					stack.push((x, y));
+				}
+			}
+		}
+		largest_cluster = largest_cluster.max(size);
+	}
+
+	let mut longest_run = 0;
+	for &(x, y) in &sites {
+		if !bad(x, y) {
+			continue;
+		}
+		let horizontal = (x..tested.len()).take_while(|&x| bad(x, y)).count();
+		let vertical = (y..tested[x].len()).take_while(|&y| bad(x, y)).count();
+		longest_run = longest_run.max(horizontal).max(vertical);
+	}
+
+	SpatialStatistics {
+		overall,
+		cluster_index,
+		clusters,
+		largest_cluster,
+		longest_run,
+		center,
+		edge,
+		quadrants,
+		join_count,
+	}
+}
+
+fn join_count(joins: usize, shared: usize, observed: usize, sites: usize, bad: usize) -> JoinCount {
+	let falling = |k: usize| {
+		(0..k)
+			.map(|i| bad.saturating_sub(i) as f64 / sites.saturating_sub(i).max(1) as f64)
+			.product::<f64>()
+	};
+	let (joins_f, shared_f) = (joins as f64, shared as f64);
+	let expected = joins_f * falling(2);
+	let variance =
+		joins_f * falling(2) + 2.0 * shared_f * falling(3) + (joins_f * (joins_f - 1.0) - 2.0 * shared_f) * falling(4)
+			- expected.powi(2);
+	let z_score = if variance > 0.0 {
+		(observed as f64 - expected) / variance.sqrt()
+	} else {
+		0.0
+	};
+
+	JoinCount {
+		joins,
+		observed,
+		expected,
+		z_score,
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::wafer::{Measurement, Site};
+
+	const SIZE: usize = 8;
+
+	fn grid(bad: impl Fn(usize, usize) -> bool) -> WaferMap {
+		let sites = (0..SIZE)
+			.map(|x| {
+				(0..SIZE)
+					.map(|y| Site {
+						die_type: DieType::Complete,
+						coord: Coordinate {
+							x: x as f32 * 10.0,
+							y: y as f32 * 10.0,
+						},
+						fault: None,
+					})
+					.collect()
+			})
+			.collect();
+		let measured = (0..SIZE)
+			.flat_map(|column| (0..SIZE).map(move |row| (column, row)))
+			.map(|(column, row)| Measurement {
+				column,
+				row,
+				bin: 1,
+				pass: !bad(column, row),
+			})
+			.collect();
+		WaferMap {
+			sites,
+			measured,
+			..WaferMap::default()
+		}
+	}
+
+	#[test]
+	fn checkerboard_is_dispersed() {
+		let statistics = spatial_statistics(&Wafer::default(), &grid(|x, y| (x + y) % 2 == 0));
+		let join_count = statistics.join_count;
+		assert_eq!(join_count.joins, 2 * SIZE * (SIZE - 1));
+		assert_eq!(join_count.observed, 0);
+		assert!(join_count.expected > 20.0);
+		assert!(join_count.z_score < -SIGNIFICANCE, "{}", join_count.z_score);
+		assert!(!join_count.is_clustered());
+		assert_eq!(statistics.clusters, SIZE * SIZE / 2);
+		assert_eq!(statistics.largest_cluster, 1);
+		assert_eq!(statistics.longest_run, 1);
+	}
+
+	#[test]
+	fn solid_block_is_clustered() {
+		let statistics = spatial_statistics(&Wafer::default(), &grid(|x, _| x < SIZE / 2));
+		let join_count = statistics.join_count;
+		assert_eq!(join_count.observed, (SIZE / 2 - 1) * SIZE + SIZE / 2 * (SIZE - 1));
+		assert!(join_count.z_score > SIGNIFICANCE, "{}", join_count.z_score);
+		assert!(join_count.is_clustered());
+		assert!(statistics.cluster_index > 1.0);
+		assert_eq!(statistics.clusters, 1);
+		assert_eq!(statistics.largest_cluster, SIZE * SIZE / 2);
+		assert_eq!(statistics.longest_run, SIZE);
+	}
+
+	#[test]
+	fn uniform_wafer_has_no_score() {
+		let join_count = spatial_statistics(&Wafer::default(), &grid(|_, _| false)).join_count;
+		assert_eq!(join_count.observed, 0);
+		assert_eq!(join_count.expected, 0.0);
+		assert_eq!(join_count.z_score, 0.0);
+	}
+}
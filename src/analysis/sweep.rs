@@ -0,0 +1,325 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+use std::fmt;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+use crate::die::DieType;
+use crate::scenario::Scenario;
+use crate::wafer::{Wafer, MAXIMUM_SCRIBE_WIDTH, MINIMUM_DIE_DIMENSION};
+
+pub const MINIMUM_SWEEP_STEPS: usize = 2;
+pub const MAXIMUM_SWEEP_STEPS: usize = 50;
+
+const DEFAULT_SWEEP_STEPS: usize = 11;
+const MAXIMUM_SWEEP_DEFECT_RATE: f32 = 100.0;
+
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub enum SweepParameters {
+	#[default]
+	DieSize,
+	DefectArea,
+	ScribeEdge,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct SweepRange {
+	pub start: f32,
+	pub end: f32,
+	pub steps: usize,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Sweep {
+	pub parameters: SweepParameters,
+	pub x: SweepRange,
+	pub y: SweepRange,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct SweepResult {
+	pub parameters: SweepParameters,
+	pub x: Vec<f32>,
+	pub y: Vec<f32>,
+	pub good: Vec<Vec<f32>>,
+}
+
+impl SweepParameters {
+	pub const ALL: &'static [SweepParameters] = &[
+		SweepParameters::DieSize,
+		SweepParameters::DefectArea,
+		SweepParameters::ScribeEdge,
+	];
+
+	pub fn labels(self) -> (&'static str, &'static str) {
+		match self {
+			SweepParameters::DieSize => ("Die Width (mm)", "Die Height (mm)"),
+			SweepParameters::DefectArea => ("D0 (/cm²)", "Die Area (mm²)"),
+			SweepParameters::ScribeEdge => ("Scribe Width (mm)", "Edge Loss (mm)"),
+		}
+	}
+
+	pub fn current(self, wafer: &Wafer) -> (f32, f32) {
+		match self {
+			SweepParameters::DieSize => (wafer.die.active_width(), wafer.die.active_height()),
+			SweepParameters::DefectArea => (wafer.defect_rate, wafer.die.active_area()),
+			SweepParameters::ScribeEdge => (wafer.scribe_lanes.0, wafer.edge_loss),
+		}
+	}
+
+	pub fn maximum(self, wafer: &Wafer) -> (f32, f32) {
+		let width = wafer.shape.max_width();
+		match self {
+			SweepParameters::DieSize => (width, width),
+			SweepParameters::DefectArea => (MAXIMUM_SWEEP_DEFECT_RATE, width * width),
+			SweepParameters::ScribeEdge => (MAXIMUM_SCRIBE_WIDTH, width / 2.0),
+		}
+	}
+
+	fn apply(self, scenario: &Scenario, x: f32, y: f32) -> Wafer {
+		let mut wafer = scenario.wafer.clone();
+		match self {
+			SweepParameters::DieSize => {
+				wafer.die = wafer.die.rectangle().new_width(x).new_height(y);
+			}
+			SweepParameters::DefectArea => {
+				let scale = (y.max(0.0) / wafer.die.active_area()).sqrt();
+				wafer.defect_rate = x.max(0.0);
+				wafer.critical_area *= scale * scale;
+				wafer.die = wafer
+					.die
+					.rectangle()
+					.new_width(wafer.die.active_width() * scale)
+					.new_height(wafer.die.active_height() * scale);
+			}
+			SweepParameters::ScribeEdge => {
+				let scribe = x.clamp(0.0, MAXIMUM_SCRIBE_WIDTH);
+				wafer.scribe_lanes = (scribe, scribe);
+				wafer.edge_loss = y.max(0.0);
+			}
+		}
+
+		if scenario.reticle_limit {
+			wafer.die = wafer.die.clamp_reticle(wafer.reticle);
+		}
+		if scenario.simple_critical_area {
+			wafer.critical_area = wafer.usable_area();
+		} else {
+			wafer.clamp_critical_area();
+		}
+		wafer.clamp_reticle_defects();
+		wafer
+	}
+}
+
+impl Display for SweepParameters {
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		match self {
+			SweepParameters::DieSize => f.write_str("Die Width × Height"),
+			SweepParameters::DefectArea => f.write_str("D0 × Die Area"),
+			SweepParameters::ScribeEdge => f.write_str("Scribe × Edge Loss"),
+		}
+	}
+}
+
+impl FromStr for SweepParameters {
+	type Err = ();
+
+	fn from_str(s: &str) -> Result<SweepParameters, ()> {
+		match s {
+			"die" => Ok(SweepParameters::DieSize),
+			"defects" => Ok(SweepParameters::DefectArea),
+			"scribe" => Ok(SweepParameters::ScribeEdge),
+			_ => Err(()),
+		}
+	}
+}
+
+impl SweepRange {
+	fn around(value: f32, minimum: f32) -> SweepRange {
+		SweepRange {
+			start: (value * 0.5).max(minimum),
+			end: (value * 1.5).max(minimum),
+			steps: DEFAULT_SWEEP_STEPS,
+		}
+	}
+
+	fn from_zero(value: f32, minimum: f32) -> SweepRange {
+		SweepRange {
+			start: 0.0,
+			end: (value * 2.0).max(minimum),
+			steps: DEFAULT_SWEEP_STEPS,
+		}
+	}
+
+	pub fn values(self) -> Vec<f32> {
+		let steps = self.steps.clamp(MINIMUM_SWEEP_STEPS, MAXIMUM_SWEEP_STEPS);
+		(0..steps)
+			.map(|i| self.start + (self.end - self.start) * i as f32 / (steps - 1) as f32)
+			.collect()
+	}
+}
+
+impl FromStr for SweepRange {
+	type Err = ();
+
+	fn from_str(s: &str) -> Result<SweepRange, ()> {
+		let fields: Vec<&str> = s.split(':').collect();
+		let [start, end, steps] = fields[..] else {
+			return Err(());
+		};
+		Ok(SweepRange {
+			start: start.parse().map_err(|_| ())?,
+			end: end.parse().map_err(|_| ())?,
+			steps: steps.parse().map_err(|_| ())?,
+		})
+	}
+}
+
+impl Sweep {
+	pub fn new(parameters: SweepParameters, wafer: &Wafer) -> Sweep {
+		let (x, y) = parameters.current(wafer);
+		let (x, y) = match parameters {
+			SweepParameters::DieSize => (
+				SweepRange::around(x, MINIMUM_DIE_DIMENSION),
+				SweepRange::around(y, MINIMUM_DIE_DIMENSION),
+			),
+			SweepParameters::DefectArea => (
+				SweepRange::from_zero(x, 0.1),
+				SweepRange::around(y, MINIMUM_DIE_DIMENSION.powi(2)),
+			),
+			SweepParameters::ScribeEdge => (SweepRange::from_zero(x, 0.2), SweepRange::from_zero(y, 5.0)),
+		};
+		Sweep { parameters, x, y }
+	}
+
+	pub fn run(&self, scenario: &Scenario) -> SweepResult {
+		let (x, y) = (self.x.values(), self.y.values());
+		let good = x
+			.iter()
+			.map(|&x| {
+				y.iter()
+					.map(|&y| {
+						let wafer = self.parameters.apply(scenario, x, y);
+						candidates(&wafer) as f32 * wafer.die_yield()
+					})
+					.collect()
+			})
+			.collect();
+
+		SweepResult {
+			parameters: self.parameters,
+			x,
+			y,
+			good,
+		}
+	}
+}
+
+fn candidates(wafer: &Wafer) -> usize {
+	wafer
+		.get_dies()
+		.iter()
+		.enumerate()
+		.flat_map(|(x, column)| {
+			column
+				.iter()
+				.enumerate()
+				.map(move |(y, (die_type, _))| (x, y, *die_type))
+		})
+		.filter(|&(x, y, die_type)| {
+			die_type == DieType::Complete && !wafer.reticle_defects.contains(&wafer.field_position(x, y))
+		})
+		.count()
+}
+
+impl SweepResult {
+	pub fn best(&self) -> Option<(f32, f32, f32)> {
+		self.x
+			.iter()
+			.zip(&self.good)
+			.flat_map(|(&x, column)| self.y.iter().zip(column).map(move |(&y, &good)| (x, y, good)))
+			.max_by(|a, b| a.2.total_cmp(&b.2))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::wafer::{Fault, WaferMap};
+
+	#[test]
+	fn range_values() {
+		let range = SweepRange {
+			start: 1.0,
+			end: 3.0,
+			steps: 5,
+		};
+		assert_eq!(range.values(), [1.0, 1.5, 2.0, 2.5, 3.0]);
+		assert_eq!(SweepRange { steps: 0, ..range }.values(), [1.0, 3.0]);
+		assert_eq!(SweepRange { steps: 1000, ..range }.values().len(), MAXIMUM_SWEEP_STEPS);
+	}
+
+	#[test]
+	fn parse_range() {
+		assert_eq!(
+			"0.5:10:20".parse(),
+			Ok(SweepRange {
+				start: 0.5,
+				end: 10.0,
+				steps: 20,
+			})
+		);
+		assert_eq!("0.5:10".parse::<SweepRange>(), Err(()));
+		assert_eq!("0.5:10:20:1".parse::<SweepRange>(), Err(()));
+		assert_eq!("0.5:ten:20".parse::<SweepRange>(), Err(()));
+		assert_eq!("0.5:10:-1".parse::<SweepRange>(), Err(()));
+	}
+
+	#[test]
+	fn apply_parameters() {
+		let scenario = Scenario::default();
+
+		let wafer = SweepParameters::DieSize.apply(&scenario, 12.0, 6.0);
+		assert_eq!((wafer.die.active_width(), wafer.die.active_height()), (12.0, 6.0));
+		assert_eq!(wafer.critical_area, wafer.usable_area());
+		let wafer = SweepParameters::DieSize.apply(&scenario, 100.0, 6.0);
+		assert!(wafer.die.active_width() < 100.0);
+
+		let wafer = SweepParameters::DefectArea.apply(&scenario, 0.2, 50.0);
+		assert_eq!(wafer.defect_rate, 0.2);
+		assert!((wafer.die.active_area() - 50.0).abs() < 1e-3);
+		let (width, height) = (scenario.wafer.die.active_width(), scenario.wafer.die.active_height());
+		assert!((wafer.die.active_width() / wafer.die.active_height() - width / height).abs() < 1e-4);
+
+		let wafer = SweepParameters::ScribeEdge.apply(&scenario, 2.0 * MAXIMUM_SCRIBE_WIDTH, 4.0);
+		assert_eq!(wafer.scribe_lanes, (MAXIMUM_SCRIBE_WIDTH, MAXIMUM_SCRIBE_WIDTH));
+		assert_eq!(wafer.edge_loss, 4.0);
+	}
+
+	#[test]
+	fn counts_candidates_without_reticle_defects() {
+		let wafer = Wafer {
+			reticle_defects: [(0, 0), (1, 2)].into(),
+			..Wafer::default()
+		};
+		let map = WaferMap::simulate(&wafer);
+		assert_eq!(
+			candidates(&wafer),
+			map.count(DieType::Complete) - map.faults(Fault::Reticle)
+		);
+
+		let scenario = Scenario {
+			wafer,
+			..Scenario::default()
+		};
+		let sweep = Sweep::new(SweepParameters::ScribeEdge, &scenario.wafer);
+		let result = sweep.run(&scenario);
+		assert_eq!((result.x.len(), result.y.len()), (sweep.x.steps, sweep.y.steps));
+		assert!(result.good.iter().all(|column| column.len() == sweep.y.steps));
+	}
+}
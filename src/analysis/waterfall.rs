@@ -0,0 +1,295 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+use std::f32::consts::PI;
+
+use crate::die::DieType;
+use crate::util::Coordinate;
+use crate::wafer::{Fault, Shape, Wafer, WaferMap, MINIMUM_DIE_DIMENSION};
+
+const SAMPLES: usize = 16;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct AreaStep {
+	pub label: &'static str,
+	pub area: f32,
+	pub total: bool,
+}
+
+pub fn area_waterfall(wafer: &Wafer, map: &WaferMap) -> Vec<AreaStep> {
+	let edge_loss = wafer.edge_loss;
+	let (substrate, usable) = match wafer.shape {
+		Shape::Wafer(diameter) => {
+			let radius = diameter.diameter() / 2.0;
+			(PI * radius.powi(2), PI * (radius - edge_loss).max(0.0).powi(2))
+		}
+		Shape::Panel(panel) => {
+			let (width, height) = panel.dimensions();
+			(
+				width * height,
+				(width - 2.0 * edge_loss).max(0.0) * (height - 2.0 * edge_loss).max(0.0),
+			)
+		}
+	};
+
+	let pitch = wafer.average_pitch();
+	let cell = |x: usize, y: usize| {
+		let site = &map.sites[x][y];
+		let width = map
+			.sites
+			.get(x + 1)
+			.map_or(pitch.0, |next| next[y].coord.x - site.coord.x);
+		let height = map.sites[x]
+			.get(y + 1)
+			.map_or(pitch.1, |next| next.coord.y - site.coord.y);
+		(site.coord, width, height)
+	};
+	let (mut sites, mut edge_sites) = (0.0, 0.0);
+	for (x, column) in map.sites.iter().enumerate() {
+		for (y, site) in column.iter().enumerate() {
+			let (top_left, width, height) = cell(x, y);
+			match site.die_type {
+				DieType::Complete => sites += usable_area(wafer, top_left, width, height),
+				DieType::Partial | DieType::Wasted => edge_sites += usable_area(wafer, top_left, width, height),
+				DieType::None => {}
+			}
+		}
+	}
+	let mut unplaced = usable - sites - edge_sites;
+
+	let secondary = (
+		wafer.secondary.width.max(MINIMUM_DIE_DIMENSION),
+		wafer.secondary.height.max(MINIMUM_DIE_DIMENSION),
+	);
+	let secondary_area = secondary.0 * secondary.1;
+	let rows: Vec<f32> = map
+		.sites
+		.first()
+		.map(|column| column.iter().map(|site| site.coord.y).collect())
+		.unwrap_or_default();
+	for site in &map.secondary {
+		let center = Coordinate {
+			x: site.coord.x + secondary.0 / 2.0,
+			y: site.coord.y + secondary.1 / 2.0,
+		};
+		let y = rows.partition_point(|&y| y <= center.y).checked_sub(1);
+		let x = y.and_then(|y| {
+			map.sites
+				.partition_point(|column| column[y].coord.x <= center.x)
+				.checked_sub(1)
+		});
+		let die_type = match (x, y) {
+			(Some(x), Some(y)) => {
+				let (top_left, width, height) = cell(x, y);
+				let inside = center.x < top_left.x + width && center.y < top_left.y + height;
+				if inside {
+					map.sites[x][y].die_type
+				} else {
+					DieType::None
+				}
+			}
+			_ => DieType::None,
+		};
+		match die_type {
+			DieType::Complete => sites -= secondary_area,
+			DieType::Partial | DieType::Wasted => edge_sites -= secondary_area,
+			DieType::None => unplaced -= secondary_area,
+		}
+	}
+
+	let complete = map.count(DieType::Complete) as f32;
+	let reticle = map.faults(Fault::Reticle) as f32;
+	let dies = complete * wafer.die.area();
+	let reserved = if complete > 0.0 { dies * reticle / complete } else { 0.0 };
+	let keep_out = if wafer.stitching.enabled {
+		let area = wafer.exposures().keep_out_area(wafer.die, wafer.stitching.keep_out);
+		(dies - reserved) * area / wafer.die.area()
+	} else {
+		0.0
+	};
+	let candidates = dies - reserved - keep_out;
+	let good = candidates * wafer.die_yield();
+
+	vec![
+		AreaStep {
+			label: "Substrate",
+			area: substrate,
+			total: true,
+		},
+		AreaStep {
+			label: "Edge Exclusion",
+			area: substrate - usable,
+			total: false,
+		},
+		AreaStep {
+			label: "Scribe Lanes",
+			area: sites - dies,
+			total: false,
+		},
+		AreaStep {
+			label: "Partial / Wasted",
+			area: edge_sites,
+			total: false,
+		},
+		AreaStep {
+			label: "Unplaced",
+			area: unplaced,
+			total: false,
+		},
+		AreaStep {
+			label: "Secondary Dies",
+			area: map.secondary.len() as f32 * secondary_area,
+			total: false,
+		},
+		AreaStep {
+			label: "Reserved Sites",
+			area: reserved,
+			total: false,
+		},
+		AreaStep {
+			label: "Stitch Keep-Out",
+			area: keep_out,
+			total: false,
+		},
+		AreaStep {
+			label: "Defect Loss",
+			area: candidates - good,
+			total: false,
+		},
+		AreaStep {
+			label: "Good Dies",
+			area: good,
+			total: true,
+		},
+	]
+}
+
+fn usable_area(wafer: &Wafer, top_left: Coordinate, width: f32, height: f32) -> f32 {
+	let edge_loss = wafer.edge_loss;
+	let (substrate_width, substrate_height) = (wafer.shape.max_width(), wafer.shape.max_height());
+	match wafer.shape {
+		Shape::Wafer(_) => {
+			let center = Coordinate {
+				x: substrate_width / 2.0,
+				y: substrate_height / 2.0,
+			};
+			let radius = substrate_width / 2.0 - edge_loss;
+			let usable = |x: f32, y: f32| {
+				Coordinate {
+					x: top_left.x + x * width,
+					y: top_left.y + y * height,
+				}
+				.within_radius(&center, radius)
+			};
+			if usable(0.0, 0.0) && usable(1.0, 0.0) && usable(0.0, 1.0) && usable(1.0, 1.0) {
+				return width * height;
+			}
+			let inside = (0..SAMPLES)
+				.flat_map(|i| (0..SAMPLES).map(move |j| (i, j)))
+				.filter(|&(i, j)| usable((i as f32 + 0.5) / SAMPLES as f32, (j as f32 + 0.5) / SAMPLES as f32))
+				.count();
+			width * height * inside as f32 / (SAMPLES * SAMPLES) as f32
+		}
+		Shape::Panel(_) => {
+			let overlap = |from: f32, length: f32, substrate: f32| {
+				((from + length).min(substrate - edge_loss) - from.max(edge_loss)).max(0.0)
+			};
+			overlap(top_left.x, width, substrate_width) * overlap(top_left.y, height, substrate_height)
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::die::Die;
+	use crate::wafer::{Panel, Reticle, Secondary, Stitching};
+
+	fn panel(die: (f32, f32)) -> Wafer {
+		Wafer {
+			shape: Shape::Panel(Panel::TwelveByTwelve),
+			reticle: Reticle::Custom {
+				width: 20.0,
+				height: 20.0,
+			},
+			edge_loss: 5.0,
+			defect_rate: 0.0,
+			scribe_lanes: (1.0, 1.0),
+			field_scribe_lanes: (1.0, 1.0),
+			die: Die::default().new_width(die.0).new_height(die.1),
+			..Wafer::default()
+		}
+	}
+
+	fn areas(steps: &[AreaStep]) -> Vec<(&'static str, f32)> {
+		steps.iter().map(|step| (step.label, step.area)).collect()
+	}
+
+	#[test]
+	fn splits_panel_area() {
+		let wafer = Wafer {
+			reticle_defects: [(0, 0)].into(),
+			..panel((9.0, 9.0))
+		};
+		let steps = area_waterfall(&wafer, &WaferMap::simulate(&wafer));
+
+		// 29 × 29 dies at a 10 mm pitch from 5.5 mm inside the usable 5..295 mm square: the first cell leaves
+		// a 0.5 mm strip unplaced on the left and top, and the last cell is clipped at 295 mm. Field position
+		// (0, 0) is every other column and row, so 15 × 15 sites are reserved.
+		let expected = [
+			("Substrate", 90000.0),
+			("Edge Exclusion", 90000.0 - 290.0 * 290.0),
+			("Scribe Lanes", 289.5 * 289.5 - 841.0 * 81.0),
+			("Partial / Wasted", 0.0),
+			("Unplaced", 290.0 * 290.0 - 289.5 * 289.5),
+			("Secondary Dies", 0.0),
+			("Reserved Sites", 225.0 * 81.0),
+			("Stitch Keep-Out", 0.0),
+			("Defect Loss", 0.0),
+			("Good Dies", 616.0 * 81.0),
+		];
+		for ((label, area), (expected_label, expected_area)) in areas(&steps).into_iter().zip(expected) {
+			assert_eq!(label, expected_label);
+			assert!(
+				(area - expected_area).abs() < 0.01,
+				"{}: {} != {}",
+				label,
+				area,
+				expected_area
+			);
+		}
+	}
+
+	#[test]
+	fn separates_secondary_dies_and_stitch_keep_out() {
+		let mut wafer = Wafer {
+			stitching: Stitching {
+				enabled: true,
+				yield_loss: 0.0,
+				keep_out: 0.5,
+			},
+			secondary: Secondary {
+				enabled: true,
+				width: 2.0,
+				height: 2.0,
+			},
+			..panel((30.0, 10.0))
+		};
+		wafer.edge_loss = 0.0;
+		let map = WaferMap::simulate(&wafer);
+		let steps = areas(&area_waterfall(&wafer, &map));
+		let area = |label: &str| steps.iter().find(|step| step.0 == label).unwrap().1;
+
+		assert!(!map.secondary.is_empty());
+		assert_eq!(area("Secondary Dies"), map.secondary.len() as f32 * 4.0);
+		let keep_out = map.count(DieType::Complete) as f32 * 2.0 * 0.5 * 10.0;
+		assert!((area("Stitch Keep-Out") - keep_out).abs() < 0.01);
+		assert!(steps.iter().all(|step| step.1 >= 0.0), "{:?}", steps);
+
+		let losses: f32 = steps[1..steps.len() - 1].iter().map(|step| step.1).sum();
+		assert!((area("Substrate") - losses - area("Good Dies")).abs() < 0.01 * area("Substrate"));
+	}
+}
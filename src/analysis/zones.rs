@@ -0,0 +1,225 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+use std::fmt;
+use std::fmt::{Display, Formatter};
+
+use crate::analysis::{site_center, tested_sites};
+use crate::die::DieType;
+use crate::wafer::{Fault, Shape, Wafer, WaferMap};
+
+pub const MINIMUM_RING_WIDTH: f32 = 1.0;
+
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub enum Zones {
+	#[default]
+	CenterMiddleEdge,
+	Rings(f32),
+}
+
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub enum ZonesOption {
+	#[default]
+	CenterMiddleEdge,
+	Rings,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Zone {
+	pub name: String,
+	pub inner: f32,
+	pub outer: f32,
+	pub complete: usize,
+	pub expected: f32,
+	pub simulated: usize,
+	pub tested: usize,
+	pub measured: usize,
+}
+
+impl Zones {
+	pub fn width(self) -> f32 {
+		match self {
+			Zones::CenterMiddleEdge => 0.0,
+			Zones::Rings(width) => width,
+		}
+	}
+
+	fn boundaries(self, radius: f32) -> Vec<(String, f32, f32)> {
+		match self {
+			Zones::CenterMiddleEdge => {
+				let (middle, edge) = (radius / 3.0f32.sqrt(), radius * (2.0f32 / 3.0).sqrt());
+				vec![
+					(String::from("Center"), 0.0, middle),
+					(String::from("Middle"), middle, edge),
+					(String::from("Edge"), edge, radius),
+				]
+			}
+			Zones::Rings(width) => {
+				let width = width.max(MINIMUM_RING_WIDTH);
+				let count = (radius / width).ceil() as usize;
+				(0..count)
+					.map(|i| {
+						let (inner, outer) = (i as f32 * width, ((i + 1) as f32 * width).min(radius));
+						(format!("{:.0}–{:.0} mm", inner, outer), inner, outer)
+					})
+					.collect()
+			}
+		}
+	}
+}
+
+impl Zone {
+	pub fn expected_yield(&self) -> Option<f32> {
+		(self.complete > 0).then(|| self.expected / self.complete as f32)
+	}
+
+	pub fn simulated_yield(&self) -> Option<f32> {
+		(self.complete > 0).then(|| self.simulated as f32 / self.complete as f32)
+	}
+
+	pub fn measured_yield(&self) -> Option<f32> {
+		(self.tested > 0).then(|| self.measured as f32 / self.tested as f32)
+	}
+}
+
+pub fn radial_zones(wafer: &Wafer, map: &WaferMap, zones: Zones) -> Vec<Zone> {
+	let radius = match wafer.shape {
+		Shape::Wafer(diameter) => diameter.diameter() / 2.0,
+		Shape::Panel(panel) => {
+			let (width, height) = panel.dimensions();
+			width.hypot(height) / 2.0
+		}
+	};
+	let mut zones: Vec<Zone> = zones
+		.boundaries(radius)
+		.into_iter()
+		.map(|(name, inner, outer)| Zone {
+			name,
+			inner,
+			outer,
+			complete: 0,
+			expected: 0.0,
+			simulated: 0,
+			tested: 0,
+			measured: 0,
+		})
+		.collect();
+
+	let die_yield = wafer.die_yield();
+	let tested = tested_sites(map);
+	for (x, column) in map.sites.iter().enumerate() {
+		for (y, site) in column.iter().enumerate() {
+			let center = site_center(wafer, map, x, y);
+			let distance = center.x.hypot(center.y);
+			let Some(zone) = zones.iter_mut().find(|zone| distance < zone.outer) else {
+				continue;
+			};
+
+			if site.die_type == DieType::Complete {
+				zone.complete += 1;
+				if site.fault != Some(Fault::Reticle) {
+					zone.expected += die_yield;
+				}
+				zone.simulated += site.is_good() as usize;
+			}
+			if !map.measured.is_empty() {
+				if let Some(pass) = tested[x][y] {
+					zone.tested += 1;
+					zone.measured += pass as usize;
+				}
+			}
+		}
+	}
+	zones
+}
+
+impl ZonesOption {
+	pub const ALL: &'static [ZonesOption] = &[ZonesOption::CenterMiddleEdge, ZonesOption::Rings];
+}
+
+impl From<Zones> for ZonesOption {
+	fn from(zones: Zones) -> ZonesOption {
+		match zones {
+			Zones::CenterMiddleEdge => ZonesOption::CenterMiddleEdge,
+			Zones::Rings(_) => ZonesOption::Rings,
+		}
+	}
+}
+
+impl Display for ZonesOption {
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		match self {
+			ZonesOption::CenterMiddleEdge => f.write_str("Center / Middle / Edge"),
+			ZonesOption::Rings => f.write_str("Fixed Width Rings"),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::wafer::Measurement;
+
+	#[test]
+	fn splits_equal_area_zones() {
+		let zones = Zones::CenterMiddleEdge.boundaries(150.0);
+		let names: Vec<&str> = zones.iter().map(|zone| zone.0.as_str()).collect();
+		assert_eq!(names, ["Center", "Middle", "Edge"]);
+		for (_, inner, outer) in &zones {
+			assert!((outer.powi(2) - inner.powi(2) - 150.0f32.powi(2) / 3.0).abs() < 0.1);
+		}
+		assert_eq!(zones[2].2, 150.0);
+	}
+
+	#[test]
+	fn splits_fixed_width_rings() {
+		let rings = Zones::Rings(40.0).boundaries(150.0);
+		let rings: Vec<(&str, f32, f32)> = rings.iter().map(|ring| (ring.0.as_str(), ring.1, ring.2)).collect();
+		assert_eq!(
+			rings,
+			[
+				("0–40 mm", 0.0, 40.0),
+				("40–80 mm", 40.0, 80.0),
+				("80–120 mm", 80.0, 120.0),
+				("120–150 mm", 120.0, 150.0),
+			]
+		);
+		assert_eq!(Zones::Rings(0.0).boundaries(150.0).len(), 150);
+	}
+
+	#[test]
+	fn counts_sites_per_zone() {
+		let wafer = Wafer {
+			edge_loss: 25.0,
+			reticle_defects: [(0, 0)].into(),
+			..Wafer::default()
+		};
+		let mut map = WaferMap::simulate(&wafer);
+		let zones = radial_zones(&wafer, &map, Zones::Rings(20.0));
+
+		let complete = map.count(DieType::Complete);
+		let candidates = complete - map.faults(Fault::Reticle);
+		assert_eq!(zones.iter().map(|zone| zone.complete).sum::<usize>(), complete);
+		assert_eq!(zones.iter().map(|zone| zone.simulated).sum::<usize>(), map.good());
+		let expected: f32 = zones.iter().map(|zone| zone.expected).sum();
+		assert!((expected - candidates as f32 * wafer.die_yield()).abs() < 0.1);
+		assert!(zones
+			.iter()
+			.all(|zone| zone.tested == 0 && zone.measured_yield().is_none()));
+		assert_eq!(zones.last().unwrap().expected_yield(), None);
+
+		let (column, row) = (map.sites.len() / 2, map.sites[0].len() / 2);
+		map.measured = vec![Measurement {
+			column,
+			row,
+			bin: 1,
+			pass: true,
+		}];
+		let zones = radial_zones(&wafer, &map, Zones::Rings(20.0));
+		assert_eq!(zones.iter().map(|zone| zone.tested).sum::<usize>(), 1);
+		assert_eq!(zones[0].measured_yield(), Some(1.0));
+	}
+}
@@ -0,0 +1,133 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+use std::path::Path;
+use std::str::FromStr;
+
+use crate::analysis::{Sweep, SweepParameters, SweepRange};
+use crate::file::{
+	export_image, export_sweep, ExportError, ImageFormat, Project, SubstrateMap, DEFAULT_IMAGE_SIZE, MAXIMUM_IMAGE_SIZE,
+};
+use crate::scenario::Scenario;
+use crate::wafer::WaferMap;
+
+const USAGE: &str = "\
+Usage:
+  die-yield-calculator
+  die-yield-calculator export <project.json> <image.svg|image.png> [--size <pixels>] [--scenario <name>]
+  die-yield-calculator map <project.json> <map.xml> [--scenario <name>]
+  die-yield-calculator sweep <project.json> <sweep.csv> [--parameters die|defects|scribe] [--x <start:end:steps>] [--y <start:end:steps>] [--scenario <name>]";
+
+pub fn run(args: &[String]) -> Result<(), String> {
+	match args.first().map(String::as_str) {
+		Some("export") => export(&args[1..]),
+		Some("map") => map(&args[1..]),
+		Some("sweep") => sweep(&args[1..]),
+		Some("help" | "--help" | "-h") => {
+			println!("{}", USAGE);
+			Ok(())
+		}
+		_ => Err(String::from(USAGE)),
+	}
+}
+
+fn export(args: &[String]) -> Result<(), String> {
+	let mut positional = Vec::new();
+	let mut size = DEFAULT_IMAGE_SIZE;
+	let mut scenario = None;
+
+	let mut args = args.iter();
+	while let Some(arg) = args.next() {
+		match arg.as_str() {
+			"--size" => size = value(&mut args, arg)?,
+			"--scenario" => scenario = Some(value::<String>(&mut args, arg)?),
+			_ => positional.push(arg),
+		}
+	}
+	let [project, output] = positional[..] else {
+		return Err(String::from(USAGE));
+	};
+
+	let project = Project::load(Path::new(project)).map_err(|error| error.to_string())?;
+	let scenario = find_scenario(&project, scenario.as_deref())?;
+	let output = Path::new(output);
+	let format =
+		ImageFormat::from_path(output).ok_or_else(|| ExportError::Format(output.display().to_string()).to_string())?;
+
+	let map = WaferMap::simulate(&scenario.wafer);
+	export_image(output, format, &scenario.wafer, &map, size.clamp(1, MAXIMUM_IMAGE_SIZE))
+		.map_err(|error| error.to_string())
+}
+
+fn map(args: &[String]) -> Result<(), String> {
+	let mut positional = Vec::new();
+	let mut scenario = None;
+
+	let mut args = args.iter();
+	while let Some(arg) = args.next() {
+		match arg.as_str() {
+			"--scenario" => scenario = Some(value::<String>(&mut args, arg)?),
+			_ => positional.push(arg),
+		}
+	}
+	let [project, output] = positional[..] else {
+		return Err(String::from(USAGE));
+	};
+
+	let project = Project::load(Path::new(project)).map_err(|error| error.to_string())?;
+	let scenario = find_scenario(&project, scenario.as_deref())?;
+	let map = WaferMap::simulate(&scenario.wafer);
+	SubstrateMap::new(&scenario.name, &scenario.wafer, &map)
+		.save(Path::new(output))
+		.map_err(|error| error.to_string())
+}
+
+fn sweep(args: &[String]) -> Result<(), String> {
+	let mut positional = Vec::new();
+	let mut parameters = SweepParameters::default();
+	let mut x: Option<SweepRange> = None;
+	let mut y: Option<SweepRange> = None;
+	let mut scenario = None;
+
+	let mut args = args.iter();
+	while let Some(arg) = args.next() {
+		match arg.as_str() {
+			"--parameters" => parameters = value(&mut args, arg)?,
+			"--x" => x = Some(value(&mut args, arg)?),
+			"--y" => y = Some(value(&mut args, arg)?),
+			"--scenario" => scenario = Some(value::<String>(&mut args, arg)?),
+			_ => positional.push(arg),
+		}
+	}
+	let [project, output] = positional[..] else {
+		return Err(String::from(USAGE));
+	};
+
+	let project = Project::load(Path::new(project)).map_err(|error| error.to_string())?;
+	let scenario = find_scenario(&project, scenario.as_deref())?;
+	let mut sweep = Sweep::new(parameters, &scenario.wafer);
+	sweep.x = x.unwrap_or(sweep.x);
+	sweep.y = y.unwrap_or(sweep.y);
+	export_sweep(Path::new(output), &sweep.run(scenario)).map_err(|error| error.to_string())
+}
+
+fn find_scenario<'a>(project: &'a Project, name: Option<&str>) -> Result<&'a Scenario, String> {
+	match name {
+		Some(name) => project
+			.scenarios
+			.iter()
+			.find(|scenario| scenario.name == name)
+			.ok_or_else(|| format!("No scenario named {}", name)),
+		None => Ok(&project.scenarios[project.active]),
+	}
+}
+
+fn value<'a, T: FromStr>(args: &mut impl Iterator<Item = &'a String>, flag: &str) -> Result<T, String> {
+	let value = args.next().ok_or_else(|| format!("Missing value for {}", flag))?;
+	value
+		.parse()
+		.map_err(|_| format!("Invalid value for {}: {}", flag, value))
+}
@@ -0,0 +1,79 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+use serde::{Deserialize, Serialize};
+
+pub const MAXIMUM_COST: f32 = 1_000_000.0;
+
+#[derive(Clone, Copy, Debug, PartialEq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct Cost {
+	pub wafer: f32,
+	pub test: f32,
+	pub packaging: f32,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct CostBreakdown {
+	pub gross_die: f32,
+	pub good_die: f32,
+	pub shipped_unit: f32,
+}
+
+impl Cost {
+	pub fn breakdown(self, gross: f32, good: f32) -> CostBreakdown {
+		let good_die = (self.wafer + self.test * gross) / good;
+		CostBreakdown {
+			gross_die: self.wafer / gross,
+			good_die,
+			shipped_unit: good_die + self.packaging,
+		}
+	}
+}
+
+impl Default for Cost {
+	fn default() -> Cost {
+		Cost {
+			wafer: 10000.0,
+			test: 0.0,
+			packaging: 0.0,
+		}
+	}
+}
+
+pub fn format_cost(cost: f32) -> String {
+	if cost.is_finite() {
+		format!("${:.2}", cost)
+	} else {
+		String::from("N/A")
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn breaks_down_cost() {
+		let cost = Cost {
+			wafer: 1000.0,
+			test: 2.0,
+			packaging: 5.0,
+		};
+		let breakdown = cost.breakdown(100.0, 80.0);
+		assert_eq!(breakdown.gross_die, 10.0);
+		assert_eq!(breakdown.good_die, 15.0);
+		assert_eq!(breakdown.shipped_unit, 20.0);
+	}
+
+	#[test]
+	fn formats_missing_costs() {
+		let breakdown = Cost::default().breakdown(0.0, 0.0);
+		assert_eq!(format_cost(breakdown.gross_die), "N/A");
+		assert_eq!(format_cost(breakdown.good_die), "N/A");
+		assert_eq!(format_cost(12.345), "$12.35");
+	}
+}
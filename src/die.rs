@@ -4,13 +4,14 @@
  * file, You can obtain one at http://mozilla.org/MPL/2.0/.
  */
 
+use serde::{Deserialize, Serialize};
+
 use crate::util::min_if;
-use crate::wafer::{MINIMUM_DIE_DIMENSION, Shape};
+use crate::wafer::{MINIMUM_DIE_DIMENSION, Reticle, Shape};
 
-pub const RETICLE_LONG: f32 = 33.0;
-pub const RETICLE_SHORT: f32 = 26.0;
+pub const MAXIMUM_DIE_BORDER: f32 = 1.0;
 
-#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Deserialize, Serialize)]
 pub enum DieType {
 	Complete,
 	Partial,
@@ -18,136 +19,218 @@ pub enum DieType {
 	None,
 }
 
-#[derive(Clone, Copy, Debug, PartialEq)]
-pub enum Die {
+#[derive(Clone, Copy, Debug, PartialEq, Deserialize, Serialize)]
+pub enum DieSize {
 	Rectangle { width: f32, height: f32 },
 	Square(f32),
 }
 
-impl Die {
+impl DieSize {
 	pub fn width(self) -> f32 {
 		match self {
-			Die::Rectangle { width, .. } => width,
-			Die::Square(width) => width,
+			DieSize::Rectangle { width, .. } => width,
+			DieSize::Square(width) => width,
 		}
 	}
 
 	pub fn height(self) -> f32 {
 		match self {
-			Die::Rectangle { height, .. } => height,
-			Die::Square(width) => width,
+			DieSize::Rectangle { height, .. } => height,
+			DieSize::Square(width) => width,
 		}
 	}
 
-	pub fn area(self) -> f32 {
-		self.width() * self.height()
-	}
-
-	pub fn width_bounds(self, reticle_limit: bool, shape: Shape) -> (f32, f32) {
-		if !reticle_limit {
-			return (MINIMUM_DIE_DIMENSION, shape.max_width());
-		}
-
-		let max = match self {
-			Die::Rectangle { height, .. } => {
-				if height > RETICLE_SHORT {
-					RETICLE_SHORT
+	fn width_max(self, long: f32, short: f32) -> f32 {
+		match self {
+			DieSize::Rectangle { height, .. } => {
+				if height > short {
+					short
 				} else {
-					RETICLE_LONG
+					long
 				}
 			}
-			Die::Square(_) => RETICLE_SHORT,
-		};
-		(MINIMUM_DIE_DIMENSION, max)
-	}
-
-	pub fn height_bounds(self, reticle_limit: bool, shape: Shape) -> (f32, f32) {
-		let min = match self {
-			Die::Rectangle { .. } => MINIMUM_DIE_DIMENSION,
-			Die::Square(width) => width,
-		};
-		if !reticle_limit {
-			return (min, shape.max_width());
+			DieSize::Square(_) => short,
 		}
+	}
 
-		let max = match self {
-			Die::Rectangle { width, .. } => {
-				if width > RETICLE_SHORT {
-					RETICLE_SHORT
+	fn height_max(self, long: f32, short: f32) -> f32 {
+		match self {
+			DieSize::Rectangle { width, .. } => {
+				if width > short {
+					short
 				} else {
-					RETICLE_LONG
+					long
 				}
 			}
-			Die::Square(width) => width,
-		};
-		(min, max)
+			DieSize::Square(width) => width,
+		}
 	}
 
-	pub fn new_width(self, width: f32) -> Die {
+	fn new_width(self, width: f32) -> DieSize {
 		let width = width.max(MINIMUM_DIE_DIMENSION);
 		match self {
-			Die::Rectangle { height, .. } => Die::Rectangle { width, height },
-			Die::Square(_) => Die::Square(width),
+			DieSize::Rectangle { height, .. } => DieSize::Rectangle { width, height },
+			DieSize::Square(_) => DieSize::Square(width),
 		}
 	}
 
-	pub fn new_height(self, height: f32) -> Die {
+	fn new_height(self, height: f32) -> DieSize {
 		let height = height.max(MINIMUM_DIE_DIMENSION);
 		match self {
-			Die::Rectangle { width, .. } => Die::Rectangle { width, height },
+			DieSize::Rectangle { width, .. } => DieSize::Rectangle { width, height },
 			square => square,
 		}
 	}
 
-	pub fn clamp_reticle(self) -> Die {
+	fn clamp(self, long: f32, short: f32) -> DieSize {
 		match self {
-			Die::Rectangle { width, height } => {
-				if width > RETICLE_LONG {
-					Die::Rectangle {
-						width: RETICLE_LONG,
-						height: height.min(RETICLE_SHORT),
+			DieSize::Rectangle { width, height } => {
+				if width > long {
+					DieSize::Rectangle {
+						width: long,
+						height: height.min(short),
 					}
-				} else if height > RETICLE_LONG {
-					Die::Rectangle {
-						width: width.min(RETICLE_SHORT),
-						height: RETICLE_LONG,
+				} else if height > long {
+					DieSize::Rectangle {
+						width: width.min(short),
+						height: long,
 					}
-				} else if width > RETICLE_SHORT {
-					Die::Rectangle {
+				} else if width > short {
+					DieSize::Rectangle {
 						width,
-						height: height.min(RETICLE_SHORT),
+						height: height.min(short),
 					}
 				} else {
-					Die::Rectangle { width, height }
+					DieSize::Rectangle { width, height }
 				}
 			}
-			Die::Square(width) => Die::Square(width.min(RETICLE_SHORT)),
+			DieSize::Square(width) => DieSize::Square(width.min(short)),
+		}
+	}
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct Die {
+	pub size: DieSize,
+	pub seal_ring: f32,
+	pub keep_out: f32,
+}
+
+impl Die {
+	pub fn border(self) -> f32 {
+		self.seal_ring + self.keep_out
+	}
+
+	pub fn active_width(self) -> f32 {
+		self.size.width()
+	}
+
+	pub fn active_height(self) -> f32 {
+		self.size.height()
+	}
+
+	pub fn active_area(self) -> f32 {
+		self.active_width() * self.active_height()
+	}
+
+	pub fn width(self) -> f32 {
+		self.active_width() + 2.0 * self.border()
+	}
+
+	pub fn height(self) -> f32 {
+		self.active_height() + 2.0 * self.border()
+	}
+
+	pub fn area(self) -> f32 {
+		self.width() * self.height()
+	}
+
+	pub fn is_square(self) -> bool {
+		matches!(self.size, DieSize::Square(_))
+	}
+
+	fn reticle_limits(self, reticle: Reticle) -> (f32, f32) {
+		let border = 2.0 * self.border();
+		(
+			(reticle.long() - border).max(MINIMUM_DIE_DIMENSION),
+			(reticle.short() - border).max(MINIMUM_DIE_DIMENSION),
+		)
+	}
+
+	pub fn width_bounds(self, reticle_limit: bool, reticle: Reticle, shape: Shape) -> (f32, f32) {
+		if !reticle_limit {
+			return (MINIMUM_DIE_DIMENSION, shape.max_width() - 2.0 * self.border());
+		}
+
+		let (long, short) = self.reticle_limits(reticle);
+		(MINIMUM_DIE_DIMENSION, self.size.width_max(long, short))
+	}
+
+	pub fn height_bounds(self, reticle_limit: bool, reticle: Reticle, shape: Shape) -> (f32, f32) {
+		let min = match self.size {
+			DieSize::Rectangle { .. } => MINIMUM_DIE_DIMENSION,
+			DieSize::Square(width) => width,
+		};
+		if !reticle_limit {
+			return (min, shape.max_width() - 2.0 * self.border());
+		}
+
+		let (long, short) = self.reticle_limits(reticle);
+		(min, self.size.height_max(long, short))
+	}
+
+	pub fn new_width(self, width: f32) -> Die {
+		Die {
+			size: self.size.new_width(width),
+			..self
+		}
+	}
+
+	pub fn new_height(self, height: f32) -> Die {
+		Die {
+			size: self.size.new_height(height),
+			..self
+		}
+	}
+
+	pub fn clamp_reticle(self, reticle: Reticle) -> Die {
+		let (long, short) = self.reticle_limits(reticle);
+		Die {
+			size: self.size.clamp(long, short),
+			..self
 		}
 	}
 
 	pub fn rectangle(self) -> Die {
-		match self {
-			Die::Square(width) => Die::Rectangle { width, height: width },
+		let size = match self.size {
+			DieSize::Square(width) => DieSize::Rectangle { width, height: width },
 			rectangle => rectangle,
-		}
+		};
+		Die { size, ..self }
 	}
 
-	pub fn square(self, reticle_limit: bool) -> Die {
-		match self {
-			Die::Rectangle { width, .. } => {
-				let width = min_if(reticle_limit, width, RETICLE_SHORT);
-				Die::Square(width)
+	pub fn square(self, reticle_limit: bool, reticle: Reticle) -> Die {
+		let size = match self.size {
+			DieSize::Rectangle { width, .. } => {
+				let width = min_if(reticle_limit, width, self.reticle_limits(reticle).1);
+				DieSize::Square(width)
 			}
 			square => square,
-		}
+		};
+		Die { size, ..self }
 	}
 }
 
 impl Default for Die {
 	fn default() -> Die {
-		Die::Rectangle {
-			width: 8.0,
-			height: 8.0,
+		Die {
+			size: DieSize::Rectangle {
+				width: 8.0,
+				height: 8.0,
+			},
+			seal_ring: 0.0,
+			keep_out: 0.0,
 		}
 	}
 }
@@ -0,0 +1,189 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+use std::fs;
+use std::path::Path;
+
+use crate::file::ImportError;
+use crate::wafer::Defect;
+
+pub const DEFECT_EXTENSIONS: &[&str] = &["klarf", "kla", "csv", "txt"];
+
+const MICROMETRES: f32 = 1000.0;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct DefectList {
+	pub name: String,
+	pub defects: Vec<Defect>,
+}
+
+impl DefectList {
+	pub fn parse(name: &str, contents: &str) -> Result<DefectList, ImportError> {
+		let defects = if contents.contains("DefectList") {
+			parse_klarf(contents)?
+		} else {
+			parse_csv(contents)?
+		};
+		Ok(DefectList {
+			name: String::from(name),
+			defects,
+		})
+	}
+
+	pub fn load(path: &Path) -> Result<DefectList, ImportError> {
+		let name = path
+			.file_name()
+			.map_or_else(String::new, |name| name.to_string_lossy().to_string());
+		DefectList::parse(&name, &fs::read_to_string(path)?)
+	}
+}
+
+fn parse_klarf(contents: &str) -> Result<Vec<Defect>, ImportError> {
+	let mut center = (0.0, 0.0);
+	let mut pitch = (0.0, 0.0);
+	let mut fields: Vec<String> = Vec::new();
+	let mut defects = Vec::new();
+
+	for statement in contents.split(';') {
+		let mut tokens = statement.split_whitespace();
+		let Some(keyword) = tokens.next() else {
+			continue;
+		};
+		let tokens: Vec<&str> = tokens.collect();
+		let number = |i: usize| {
+			tokens
+				.get(i)
+				.and_then(|token| token.parse::<f32>().ok())
+				.ok_or_else(|| ImportError::Format(format!("Invalid {}", keyword)))
+		};
+
+		match keyword {
+			"SampleCenterLocation" => center = (number(0)?, number(1)?),
+			"DiePitch" => pitch = (number(0)?, number(1)?),
+			"DefectRecordSpec" => fields = tokens.iter().skip(1).map(|field| field.to_ascii_uppercase()).collect(),
+			"DefectList" => {
+				if fields.is_empty() {
+					return Err(ImportError::Format(String::from("Missing DefectRecordSpec")));
+				}
+				let column = |name: &str| fields.iter().position(|field| field == name);
+				let (Some(x_rel), Some(y_rel)) = (column("XREL"), column("YREL")) else {
+					return Err(ImportError::Format(String::from("Missing XREL or YREL")));
+				};
+				let (x_index, y_index) = (column("XINDEX"), column("YINDEX"));
+				let (size, x_size, y_size) = (column("DSIZE"), column("XSIZE"), column("YSIZE"));
+
+				for record in tokens.chunks(fields.len()) {
+					let value = |i: Option<usize>| {
+						i.and_then(|i| record.get(i))
+							.and_then(|token| token.parse::<f32>().ok())
+							.unwrap_or(0.0)
+					};
+					let x = value(x_index) * pitch.0 + value(Some(x_rel)) - center.0;
+					let y = value(y_index) * pitch.1 + value(Some(y_rel)) - center.1;
+					let size = if size.is_some() {
+						value(size)
+					} else {
+						value(x_size).max(value(y_size))
+					};
+					defects.push(Defect {
+						x: x / MICROMETRES,
+						y: y / MICROMETRES,
+						size: size / MICROMETRES,
+					});
+				}
+			}
+			_ => {}
+		}
+	}
+	Ok(defects)
+}
+
+fn parse_csv(contents: &str) -> Result<Vec<Defect>, ImportError> {
+	let mut defects = Vec::new();
+	for (i, line) in contents.lines().enumerate() {
+		let fields: Vec<&str> = line.split([',', ';', '\t']).map(str::trim).collect();
+		if fields.iter().all(|field| field.is_empty()) {
+			continue;
+		}
+		let number = |i: usize| fields.get(i).and_then(|field| field.parse::<f32>().ok());
+		match (number(0), number(1)) {
+			(Some(x), Some(y)) => defects.push(Defect {
+				x,
+				y,
+				size: number(2).unwrap_or(0.0),
+			}),
+			_ if i == 0 => continue,
+			_ => return Err(ImportError::Format(format!("Invalid line {}: {}", i + 1, line))),
+		}
+	}
+	Ok(defects)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn parse_klarf() {
+		let klarf = "FileVersion 1 2;\nSampleCenterLocation 100000 100000;\nDiePitch 10000 10000;\n\
+			DefectRecordSpec 6 DEFECTID XREL YREL XINDEX YINDEX DSIZE;\n\
+			DefectList\n 1 500 2500 10 10 3.5\n 2 1000 0 9 11 0;\nEndOfFile;\n";
+		let list = DefectList::parse("lot.klarf", klarf).unwrap();
+		assert_eq!(list.name, "lot.klarf");
+		assert_eq!(
+			list.defects,
+			vec![
+				Defect {
+					x: 0.5,
+					y: 2.5,
+					size: 0.0035,
+				},
+				Defect {
+					x: -9.0,
+					y: 10.0,
+					size: 0.0,
+				},
+			]
+		);
+	}
+
+	#[test]
+	fn parse_klarf_sizes() {
+		let klarf = "DefectRecordSpec 5 defectid xrel yrel xsize ysize;\nDefectList 1 -2000 4000 2 6;\n";
+		let list = DefectList::parse("lot.klarf", klarf).unwrap();
+		assert_eq!(
+			list.defects,
+			vec![Defect {
+				x: -2.0,
+				y: 4.0,
+				size: 0.006,
+			}]
+		);
+		assert!(DefectList::parse("lot.klarf", "DefectList 1 0 0;\n").is_err());
+		assert!(DefectList::parse("lot.klarf", "DefectRecordSpec 2 DEFECTID XREL;\nDefectList 1 0;\n").is_err());
+	}
+
+	#[test]
+	fn parse_csv() {
+		let list = DefectList::parse("lot.csv", "x,y,size\n1.5,-2,0.1\n\n3;4\n").unwrap();
+		assert_eq!(
+			list.defects,
+			vec![
+				Defect {
+					x: 1.5,
+					y: -2.0,
+					size: 0.1,
+				},
+				Defect {
+					x: 3.0,
+					y: 4.0,
+					size: 0.0,
+				},
+			]
+		);
+		assert!(DefectList::parse("lot.csv", "x,y\n1,2\none,2\n").is_err());
+	}
+}
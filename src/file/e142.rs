@@ -0,0 +1,553 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+use std::fmt::Write;
+use std::fs;
+use std::path::Path;
+
+use roxmltree::{Document, Node};
+
+use crate::die::{Die, DieSize, DieType};
+use crate::file::{ExportError, ImportError};
+use crate::util::Coordinate;
+use crate::wafer::{Diameter, Fault, Panel, Shape, Site, Wafer, WaferMap};
+
+pub const E142_EXTENSION: &str = "xml";
+
+const NAMESPACE: &str = "urn:semi-org:xsd.E142-1.V1005.SubstrateMap";
+const NULL_BIN: &str = "FF";
+
+const GOOD_BIN: u8 = 0x01;
+const DEFECTIVE_BIN: u8 = 0x02;
+const RETICLE_DEFECT_BIN: u8 = 0x03;
+const PARTIAL_BIN: u8 = 0x04;
+const WASTED_BIN: u8 = 0x05;
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum BinQuality {
+	Pass,
+	Fail,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct BinDefinition {
+	pub code: u8,
+	pub quality: BinQuality,
+	pub description: String,
+	pub count: usize,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct SubstrateMap {
+	pub substrate_id: String,
+	pub shape: Shape,
+	pub die: (f32, f32),
+	pub step: (f32, f32),
+	pub lower_left: (f32, f32),
+	pub bins: Vec<BinDefinition>,
+	pub codes: Vec<Vec<Option<u8>>>,
+}
+
+impl BinQuality {
+	fn as_str(self) -> &'static str {
+		match self {
+			BinQuality::Pass => "Pass",
+			BinQuality::Fail => "Fail",
+		}
+	}
+}
+
+impl SubstrateMap {
+	pub fn new(substrate_id: &str, wafer: &Wafer, map: &WaferMap) -> SubstrateMap {
+		let columns = map.sites.len();
+		let rows = map.sites.first().map_or(0, Vec::len);
+		let die = (wafer.die.width(), wafer.die.height());
+		let step = (die.0 + wafer.scribe_lanes.0, die.1 + wafer.scribe_lanes.1);
+		let lower_left = match map.sites.first().and_then(|column| column.last()) {
+			Some(site) => (
+				site.coord.x - wafer.shape.max_width() / 2.0,
+				wafer.shape.max_height() / 2.0 - site.coord.y - die.1,
+			),
+			None => (0.0, 0.0),
+		};
+
+		let codes: Vec<Vec<Option<u8>>> = (0..rows)
+			.map(|y| (0..columns).map(|x| bin_code(&map.sites[x][y])).collect())
+			.collect();
+		let bins = [
+			(GOOD_BIN, BinQuality::Pass, "Good Die"),
+			(DEFECTIVE_BIN, BinQuality::Fail, "Defective Die"),
+			(RETICLE_DEFECT_BIN, BinQuality::Fail, "Reticle Defect"),
+			(PARTIAL_BIN, BinQuality::Fail, "Partial Die"),
+			(WASTED_BIN, BinQuality::Fail, "Wasted Die"),
+		]
+		.into_iter()
+		.map(|(code, quality, description)| BinDefinition {
+			code,
+			quality,
+			description: String::from(description),
+			count: codes.iter().flatten().filter(|bin| **bin == Some(code)).count(),
+		})
+		.collect();
+
+		SubstrateMap {
+			substrate_id: String::from(substrate_id),
+			shape: wafer.shape,
+			die,
+			step,
+			lower_left,
+			bins,
+			codes,
+		}
+	}
+
+	pub fn dimension(&self) -> (usize, usize) {
+		(self.codes.first().map_or(0, Vec::len), self.codes.len())
+	}
+
+	pub fn good(&self) -> usize {
+		self.bins
+			.iter()
+			.filter(|bin| bin.quality == BinQuality::Pass)
+			.map(|bin| bin.count)
+			.sum()
+	}
+
+	pub fn to_xml(&self) -> String {
+		let (columns, rows) = self.dimension();
+		let (substrate_type, size) = match self.shape {
+			Shape::Wafer(diameter) => ("Wafer", (diameter.diameter(), diameter.diameter())),
+			Shape::Panel(panel) => ("Panel", panel.dimensions()),
+		};
+		let id = escape(&self.substrate_id);
+
+		let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+		let _ = writeln!(xml, "<MapData xmlns=\"{}\">", NAMESPACE);
+		xml.push_str("\t<Layouts>\n");
+		let _ = writeln!(
+			xml,
+			"\t\t<Layout LayoutId=\"{}\" DefaultUnits=\"mm\" TopLevel=\"true\">",
+			substrate_type
+		);
+		xml.push_str("\t\t\t<Dimension X=\"1\" Y=\"1\"/>\n");
+		let _ = writeln!(xml, "\t\t\t<DeviceSize X=\"{}\" Y=\"{}\"/>", size.0, size.1);
+		xml.push_str("\t\t\t<ChildLayouts>\n\t\t\t\t<ChildLayout LayoutId=\"Devices\"/>\n\t\t\t</ChildLayouts>\n");
+		xml.push_str("\t\t</Layout>\n");
+		xml.push_str("\t\t<Layout LayoutId=\"Devices\" DefaultUnits=\"mm\">\n");
+		let _ = writeln!(xml, "\t\t\t<Dimension X=\"{}\" Y=\"{}\"/>", columns, rows);
+		xml.push_str(concat!(
+			"\t\t\t<!-- LowerLeft is the lower left corner of the lower left device in mm, ",
+			"measured from the substrate centre with X right, Y up and the notch down -->\n",
+		));
+		let _ = writeln!(
+			xml,
+			"\t\t\t<LowerLeft X=\"{:.4}\" Y=\"{:.4}\"/>",
+			self.lower_left.0, self.lower_left.1
+		);
+		let _ = writeln!(
+			xml,
+			"\t\t\t<DeviceSize X=\"{:.4}\" Y=\"{:.4}\"/>",
+			self.die.0, self.die.1
+		);
+		let _ = writeln!(
+			xml,
+			"\t\t\t<StepSize X=\"{:.4}\" Y=\"{:.4}\"/>",
+			self.step.0, self.step.1
+		);
+		xml.push_str("\t\t</Layout>\n");
+		xml.push_str("\t</Layouts>\n");
+
+		xml.push_str("\t<Substrates>\n");
+		let _ = writeln!(
+			xml,
+			"\t\t<Substrate SubstrateType=\"{}\" SubstrateId=\"{}\">",
+			substrate_type, id
+		);
+		let _ = writeln!(xml, "\t\t\t<GoodDevices>{}</GoodDevices>", self.good());
+		xml.push_str("\t\t\t<SupplierName>Die Yield Calculator</SupplierName>\n");
+		xml.push_str("\t\t</Substrate>\n");
+		xml.push_str("\t</Substrates>\n");
+
+		xml.push_str("\t<SubstrateMaps>\n");
+		let _ = writeln!(
+			xml,
+			concat!(
+				"\t\t<SubstrateMap SubstrateType=\"{}\" SubstrateId=\"{}\" LayoutSpecifier=\"{}/Devices\" ",
+				"SubstrateSide=\"TopSide\" OriginLocation=\"UpperLeft\" AxisDirection=\"DownRight\">",
+			),
+			substrate_type, id, substrate_type
+		);
+		xml.push_str("\t\t\t<Overlay MapName=\"Bins\" MapVersion=\"1\">\n");
+		let _ = writeln!(
+			xml,
+			"\t\t\t\t<BinCodeMap BinType=\"HexaDecimal\" NullBin=\"{}\">",
+			NULL_BIN
+		);
+		xml.push_str("\t\t\t\t\t<BinDefinitions>\n");
+		for bin in &self.bins {
+			let _ = writeln!(
+				xml,
+				"\t\t\t\t\t\t<BinDefinition BinCode=\"{:02X}\" BinCount=\"{}\" BinQuality=\"{}\" BinDescription=\"{}\"/>",
+				bin.code,
+				bin.count,
+				bin.quality.as_str(),
+				escape(&bin.description)
+			);
+		}
+		xml.push_str("\t\t\t\t\t</BinDefinitions>\n");
+		for row in &self.codes {
+			xml.push_str("\t\t\t\t\t<BinCode>");
+			for code in row {
+				match code {
+					Some(code) => {
+						let _ = write!(xml, "{:02X}", code);
+					}
+					None => xml.push_str(NULL_BIN),
+				}
+			}
+			xml.push_str("</BinCode>\n");
+		}
+		xml.push_str("\t\t\t\t</BinCodeMap>\n");
+		xml.push_str("\t\t\t</Overlay>\n");
+		xml.push_str("\t\t</SubstrateMap>\n");
+		xml.push_str("\t</SubstrateMaps>\n");
+		xml.push_str("</MapData>\n");
+		xml
+	}
+
+	pub fn from_xml(xml: &str) -> Result<SubstrateMap, ImportError> {
+		let document = Document::parse(xml)?;
+		let root = document.root_element();
+
+		let substrate_map = element(root, "SubstrateMap")?;
+		let substrate_type = substrate_map.attribute("SubstrateType").unwrap_or("Wafer");
+		let substrate_id = substrate_map.attribute("SubstrateId").unwrap_or_default();
+		let specifier = substrate_map.attribute("LayoutSpecifier").unwrap_or_default();
+		let direction = substrate_map.attribute("AxisDirection").unwrap_or("DownRight");
+
+		let layouts: Vec<Node> = root.descendants().filter(|node| node.has_tag_name("Layout")).collect();
+		let layout = |id: &str| {
+			layouts
+				.iter()
+				.copied()
+				.find(|layout| layout.attribute("LayoutId") == Some(id))
+				.ok_or_else(|| ImportError::Format(format!("Missing layout {}", id)))
+		};
+		let mut ids = specifier.split('/').filter(|id| !id.is_empty());
+		let top = layout(ids.next().unwrap_or(substrate_type))?;
+		let devices = layout(ids.last().unwrap_or("Devices"))?;
+
+		let size = point(top, "DeviceSize")?;
+		let shape = match substrate_type {
+			"Panel" => Shape::Panel(nearest_panel(size)),
+			_ => Shape::Wafer(nearest_diameter(size.0)),
+		};
+		let die = point(devices, "DeviceSize")?;
+		let step = point(devices, "StepSize").unwrap_or(die);
+		if !(die.0 > 0.0 && die.1 > 0.0) {
+			return Err(ImportError::Format(format!("Invalid DeviceSize {} x {}", die.0, die.1)));
+		}
+		if !(step.0 >= die.0 && step.1 >= die.1) {
+			return Err(ImportError::Format(format!(
+				"StepSize {} x {} is smaller than DeviceSize {} x {}",
+				step.0, step.1, die.0, die.1
+			)));
+		}
+		let lower_left = point(devices, "LowerLeft").unwrap_or((0.0, 0.0));
+
+		let bin_map = element(substrate_map, "BinCodeMap")?;
+		let null_bin = bin_map.attribute("NullBin").unwrap_or(NULL_BIN);
+		let decimal = bin_map.attribute("BinType") == Some("Decimal");
+		let width = if decimal { 3 } else { 2 };
+		let parse = |code: &str| {
+			let radix = if decimal { 10 } else { 16 };
+			u8::from_str_radix(code, radix).map_err(|_| ImportError::Format(format!("Invalid bin code {}", code)))
+		};
+
+		let mut bins = Vec::new();
+		for definition in bin_map.descendants().filter(|node| node.has_tag_name("BinDefinition")) {
+			let code = definition.attribute("BinCode").unwrap_or_default();
+			bins.push(BinDefinition {
+				code: parse(code)?,
+				quality: match definition.attribute("BinQuality") {
+					Some("Pass") => BinQuality::Pass,
+					_ => BinQuality::Fail,
+				},
+				description: definition.attribute("BinDescription").unwrap_or_default().to_string(),
+				count: 0,
+			});
+		}
+
+		let mut codes = Vec::new();
+		for line in bin_map.children().filter(|node| node.has_tag_name("BinCode")) {
+			let line: Vec<char> = line.text().unwrap_or_default().trim().chars().collect();
+			if line.len() % width != 0 {
+				return Err(ImportError::Format(format!(
+					"Bin code row {} is not a multiple of {} characters",
+					codes.len() + 1,
+					width
+				)));
+			}
+			let row = line
+				.chunks(width)
+				.map(|chunk| {
+					let code: String = chunk.iter().collect();
+					if code == null_bin {
+						Ok(None)
+					} else {
+						parse(&code).map(Some)
+					}
+				})
+				.collect::<Result<Vec<Option<u8>>, ImportError>>()?;
+			codes.push(row);
+		}
+		let columns = codes.iter().map(Vec::len).max().unwrap_or(0);
+		for row in &mut codes {
+			row.resize(columns, None);
+		}
+
+		if direction.starts_with("Up") {
+			codes.reverse();
+		}
+		if direction.ends_with("Left") {
+			for row in &mut codes {
+				row.reverse();
+			}
+		}
+
+		for bin in &mut bins {
+			bin.count = codes.iter().flatten().filter(|code| **code == Some(bin.code)).count();
+		}
+
+		Ok(SubstrateMap {
+			substrate_id: substrate_id.to_string(),
+			shape,
+			die,
+			step,
+			lower_left,
+			bins,
+			codes,
+		})
+	}
+
+	pub fn wafer_map(&self) -> (Wafer, WaferMap) {
+		let wafer = Wafer {
+			shape: self.shape,
+			die: Die {
+				size: DieSize::Rectangle {
+					width: self.die.0,
+					height: self.die.1,
+				},
+				..Die::default()
+			},
+			critical_area: self.die.0 * self.die.1,
+			scribe_lanes: (self.step.0 - self.die.0, self.step.1 - self.die.1),
+			field_scribe_lanes: (self.step.0 - self.die.0, self.step.1 - self.die.1),
+			..Wafer::default()
+		};
+
+		let (columns, rows) = self.dimension();
+		let left = self.shape.max_width() / 2.0 + self.lower_left.0;
+		let top =
+			self.shape.max_height() / 2.0 - self.lower_left.1 - self.die.1 - (rows.max(1) - 1) as f32 * self.step.1;
+		let sites = (0..columns)
+			.map(|x| {
+				(0..rows)
+					.map(|y| {
+						let (die_type, fault) = self.site(self.codes[y][x]);
+						Site {
+							die_type,
+							coord: Coordinate {
+								x: left + x as f32 * self.step.0,
+								y: top + y as f32 * self.step.1,
+							},
+							fault,
+						}
+					})
+					.collect()
+			})
+			.collect();
+
+		(
+			wafer,
+			WaferMap {
+				sites,
+				secondary: Vec::new(),
+				measured: Vec::new(),
+				inspection: None,
+			},
+		)
+	}
+
+	fn site(&self, code: Option<u8>) -> (DieType, Option<Fault>) {
+		let Some(code) = code else {
+			return (DieType::None, None);
+		};
+		match code {
+			PARTIAL_BIN => (DieType::Partial, None),
+			WASTED_BIN => (DieType::Wasted, None),
+			RETICLE_DEFECT_BIN => (DieType::Complete, Some(Fault::Reticle)),
+			_ => {
+				let pass = self
+					.bins
+					.iter()
+					.find(|bin| bin.code == code)
+					.map_or(code == GOOD_BIN, |bin| bin.quality == BinQuality::Pass);
+				(DieType::Complete, (!pass).then_some(Fault::Random))
+			}
+		}
+	}
+
+	pub fn save(&self, path: &Path) -> Result<(), ExportError> {
+		fs::write(path, self.to_xml())?;
+		Ok(())
+	}
+
+	pub fn load(path: &Path) -> Result<SubstrateMap, ImportError> {
+		SubstrateMap::from_xml(&fs::read_to_string(path)?)
+	}
+}
+
+fn bin_code(site: &Site) -> Option<u8> {
+	match (site.die_type, site.fault) {
+		(DieType::Complete, None) => Some(GOOD_BIN),
+		(DieType::Complete, Some(Fault::Random)) => Some(DEFECTIVE_BIN),
+		(DieType::Complete, Some(Fault::Reticle)) => Some(RETICLE_DEFECT_BIN),
+		(DieType::Partial, _) => Some(PARTIAL_BIN),
+		(DieType::Wasted, _) => Some(WASTED_BIN),
+		(DieType::None, _) => None,
+	}
+}
+
+fn element<'a, 'input>(node: Node<'a, 'input>, name: &str) -> Result<Node<'a, 'input>, ImportError> {
+	node.descendants()
+		.find(|node| node.has_tag_name(name))
+		.ok_or_else(|| ImportError::Format(format!("Missing {}", name)))
+}
+
+fn point(node: Node, name: &str) -> Result<(f32, f32), ImportError> {
+	let point = node
+		.children()
+		.find(|node| node.has_tag_name(name))
+		.ok_or_else(|| ImportError::Format(format!("Missing {}", name)))?;
+	let coordinate = |axis: &str| {
+		point
+			.attribute(axis)
+			.and_then(|value| value.trim().parse::<f32>().ok())
+			.ok_or_else(|| ImportError::Format(format!("Invalid {} {}", name, axis)))
+	};
+	Ok((coordinate("X")?, coordinate("Y")?))
+}
+
+fn nearest_diameter(size: f32) -> Diameter {
+	Diameter::ALL
+		.iter()
+		.copied()
+		.min_by(|a, b| (a.diameter() - size).abs().total_cmp(&(b.diameter() - size).abs()))
+		.unwrap_or_default()
+}
+
+fn nearest_panel(size: (f32, f32)) -> Panel {
+	let distance = |panel: &Panel| {
+		let (width, height) = panel.dimensions();
+		(width - size.0).abs() + (height - size.1).abs()
+	};
+	Panel::ALL
+		.iter()
+		.copied()
+		.min_by(|a, b| distance(a).total_cmp(&distance(b)))
+		.unwrap_or_default()
+}
+
+fn escape(value: &str) -> String {
+	value
+		.replace('&', "&amp;")
+		.replace('<', "&lt;")
+		.replace('>', "&gt;")
+		.replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn assert_close(a: (f32, f32), b: (f32, f32)) {
+		assert!(
+			(a.0 - b.0).abs() < 1e-3 && (a.1 - b.1).abs() < 1e-3,
+			"{:?} != {:?}",
+			a,
+			b
+		);
+	}
+
+	#[test]
+	fn round_trip() {
+		let wafer = Wafer {
+			scribe_lanes: (0.1, 0.2),
+			field_scribe_lanes: (0.1, 0.2),
+			reticle_defects: [(0, 0)].into(),
+			..Wafer::default()
+		};
+		let map = WaferMap::simulate(&wafer);
+		let written = SubstrateMap::new("W01 & <test>", &wafer, &map);
+		let read = SubstrateMap::from_xml(&written.to_xml()).unwrap();
+
+		assert_eq!(read.substrate_id, written.substrate_id);
+		assert_eq!(read.shape, written.shape);
+		assert_eq!(read.codes, written.codes);
+		assert_eq!(read.bins, written.bins);
+		assert_eq!(read.good(), map.good());
+		assert_close(read.die, written.die);
+		assert_close(read.step, written.step);
+		assert_close(read.lower_left, written.lower_left);
+
+		let (_, read_map) = read.wafer_map();
+		for (column, read_column) in map.sites.iter().zip(&read_map.sites) {
+			for (site, read_site) in column.iter().zip(read_column) {
+				assert_eq!(site.die_type, read_site.die_type);
+				assert_eq!(site.fault, read_site.fault);
+				if site.die_type != DieType::None {
+					assert_close((site.coord.x, site.coord.y), (read_site.coord.x, read_site.coord.y));
+				}
+			}
+		}
+	}
+
+	#[test]
+	fn rejects_partial_bin_code() {
+		let wafer = Wafer::default();
+		let xml = SubstrateMap::new("W01", &wafer, &WaferMap::simulate(&wafer)).to_xml();
+		let xml = xml.replacen("<BinCode>", "<BinCode>0", 1);
+		assert!(matches!(SubstrateMap::from_xml(&xml), Err(ImportError::Format(_))));
+	}
+
+	#[test]
+	fn rejects_invalid_device_and_step_sizes() {
+		let wafer = Wafer::default();
+		let xml = SubstrateMap::new("W01", &wafer, &WaferMap::simulate(&wafer)).to_xml();
+		let die = format!(
+			"<DeviceSize X=\"{:.4}\" Y=\"{:.4}\"/>",
+			wafer.die.width(),
+			wafer.die.height()
+		);
+		let step = format!(
+			"<StepSize X=\"{:.4}\" Y=\"{:.4}\"/>",
+			wafer.die.width() + wafer.scribe_lanes.0,
+			wafer.die.height() + wafer.scribe_lanes.1
+		);
+		assert!(xml.contains(&die) && xml.contains(&step));
+
+		for (from, to) in [
+			(&die, "<DeviceSize X=\"0\" Y=\"1\"/>"),
+			(&die, "<DeviceSize X=\"-1\" Y=\"1\"/>"),
+			(&step, "<StepSize X=\"0\" Y=\"0\"/>"),
+			(&step, "<StepSize X=\"1\" Y=\"100\"/>"),
+		] {
+			let invalid = xml.replace(from.as_str(), to);
+			assert!(matches!(SubstrateMap::from_xml(&invalid), Err(ImportError::Format(_))));
+		}
+	}
+}
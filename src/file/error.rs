@@ -0,0 +1,64 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+use std::fmt::{Display, Formatter};
+use std::{fmt, io};
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum ExportError {
+	Io(String),
+	Format(String),
+	Encode(String),
+}
+
+impl Display for ExportError {
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		match self {
+			ExportError::Io(error) => write!(f, "Could not write file: {}", error),
+			ExportError::Format(error) => write!(f, "Unsupported format: {}", error),
+			ExportError::Encode(error) => write!(f, "Could not encode file: {}", error),
+		}
+	}
+}
+
+impl From<io::Error> for ExportError {
+	fn from(error: io::Error) -> ExportError {
+		ExportError::Io(error.to_string())
+	}
+}
+
+impl From<serde_json::Error> for ExportError {
+	fn from(error: serde_json::Error) -> ExportError {
+		ExportError::Encode(error.to_string())
+	}
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum ImportError {
+	Io(String),
+	Format(String),
+}
+
+impl Display for ImportError {
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		match self {
+			ImportError::Io(error) => write!(f, "Could not read file: {}", error),
+			ImportError::Format(error) => write!(f, "Invalid file: {}", error),
+		}
+	}
+}
+
+impl From<io::Error> for ImportError {
+	fn from(error: io::Error) -> ImportError {
+		ImportError::Io(error.to_string())
+	}
+}
+
+impl From<roxmltree::Error> for ImportError {
+	fn from(error: roxmltree::Error) -> ImportError {
+		ImportError::Format(error.to_string())
+	}
+}
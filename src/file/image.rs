@@ -0,0 +1,93 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+use std::fs;
+use std::path::Path;
+
+use iced::Size;
+use resvg::{tiny_skia, usvg};
+use resvg::usvg::{fontdb, PostProcessingSteps, TreeParsing, TreePostProc};
+
+use crate::file::ExportError;
+use crate::render::{draw_wafer, Svg};
+use crate::wafer::{Wafer, WaferMap};
+
+pub const DEFAULT_IMAGE_SIZE: u32 = 1024;
+pub const MAXIMUM_IMAGE_SIZE: u32 = 8192;
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ImageFormat {
+	Svg,
+	Png,
+}
+
+impl ImageFormat {
+	pub fn extension(self) -> &'static str {
+		match self {
+			ImageFormat::Svg => "svg",
+			ImageFormat::Png => "png",
+		}
+	}
+
+	pub fn from_path(path: &Path) -> Option<ImageFormat> {
+		match path.extension()?.to_str()?.to_ascii_lowercase().as_str() {
+			"svg" => Some(ImageFormat::Svg),
+			"png" => Some(ImageFormat::Png),
+			_ => None,
+		}
+	}
+}
+
+pub fn render_svg(wafer: &Wafer, map: &WaferMap, size: u32) -> String {
+	let bounds = Size::new(size as f32, size as f32);
+	let mut svg = Svg::new(bounds);
+	draw_wafer(&mut svg, bounds, wafer, map);
+	svg.finish()
+}
+
+pub fn render_png(wafer: &Wafer, map: &WaferMap, size: u32) -> Result<Vec<u8>, ExportError> {
+	let svg = render_svg(wafer, map, size);
+	let mut tree = usvg::Tree::from_str(&svg, &usvg::Options::default())
+		.map_err(|error| ExportError::Encode(error.to_string()))?;
+
+	let mut fonts = fontdb::Database::new();
+	fonts.load_system_fonts();
+	let sans_serif = fontdb::Query {
+		families: &[fontdb::Family::SansSerif],
+		..fontdb::Query::default()
+	};
+	if fonts.query(&sans_serif).is_none() {
+		let families: Vec<String> = fonts
+			.faces()
+			.flat_map(|face| face.families.iter().map(|(family, _)| family.clone()))
+			.collect();
+		let fallback = families
+			.iter()
+			.find(|family| family.contains("Sans"))
+			.or(families.first());
+		if let Some(family) = fallback {
+			fonts.set_sans_serif_family(family.clone());
+		}
+	}
+	tree.postprocess(PostProcessingSteps::default(), &fonts);
+
+	let mut pixmap = tiny_skia::Pixmap::new(size, size)
+		.ok_or_else(|| ExportError::Encode(format!("Invalid image size {}", size)))?;
+	resvg::render(&tree, tiny_skia::Transform::default(), &mut pixmap.as_mut());
+	pixmap
+		.encode_png()
+		.map_err(|error| ExportError::Encode(error.to_string()))
+}
+
+pub fn export_image(
+	path: &Path, format: ImageFormat, wafer: &Wafer, map: &WaferMap, size: u32,
+) -> Result<(), ExportError> {
+	match format {
+		ImageFormat::Svg => fs::write(path, render_svg(wafer, map, size))?,
+		ImageFormat::Png => fs::write(path, render_png(wafer, map, size)?)?,
+	}
+	Ok(())
+}
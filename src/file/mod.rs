@@ -0,0 +1,23 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+pub use defects::*;
+pub use e142::*;
+pub use error::*;
+pub use image::*;
+pub use probe::*;
+pub use project::*;
+pub use sites::*;
+pub use sweep::*;
+
+mod defects;
+mod e142;
+mod error;
+mod image;
+mod probe;
+mod project;
+mod sites;
+mod sweep;
@@ -0,0 +1,258 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::Path;
+
+use crate::die::DieType;
+use crate::file::{prober_indices, ImportError, IndexOrigin, Notch, ProberConvention};
+use crate::wafer::{Measurement, Wafer, WaferMap};
+
+pub const PROBE_EXTENSIONS: &[&str] = &["csv", "sinf", "txt"];
+
+const EMPTY_BINS: &[&str] = &["__", "@@", ".", ""];
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ProbeMap {
+	pub name: String,
+	pub dies: BTreeMap<(i32, i32), (u32, bool)>,
+	pub convention: Option<ProberConvention>,
+}
+
+impl ProbeMap {
+	pub fn parse(name: &str, contents: &str) -> Result<ProbeMap, ImportError> {
+		if contents.lines().any(|line| line.trim_start().starts_with("RowData:")) {
+			ProbeMap::parse_sinf(name, contents)
+		} else {
+			ProbeMap::parse_csv(name, contents)
+		}
+	}
+
+	fn parse_csv(name: &str, contents: &str) -> Result<ProbeMap, ImportError> {
+		let mut dies = BTreeMap::new();
+		for (i, line) in contents.lines().enumerate() {
+			let fields: Vec<&str> = line.split([',', ';', '\t']).map(str::trim).collect();
+			if fields.iter().all(|field| field.is_empty()) {
+				continue;
+			}
+			let invalid = || ImportError::Format(format!("Invalid line {}: {}", i + 1, line));
+			let (Some(x), Some(y), Some(bin)) = (fields.first(), fields.get(1), fields.get(2)) else {
+				return Err(invalid());
+			};
+			let (Ok(x), Ok(y), Ok(bin)) = (x.parse::<i32>(), y.parse::<i32>(), bin.parse::<u32>()) else {
+				if i == 0 {
+					continue;
+				}
+				return Err(invalid());
+			};
+			let pass = match fields.get(3).map(|pass| pass.to_ascii_lowercase()) {
+				Some(pass) if !pass.is_empty() => matches!(pass.as_str(), "1" | "true" | "pass" | "p"),
+				_ => bin == 1,
+			};
+			dies.insert((x, y), (bin, pass));
+		}
+
+		Ok(ProbeMap {
+			name: String::from(name),
+			dies,
+			convention: None,
+		})
+	}
+
+	fn parse_sinf(name: &str, contents: &str) -> Result<ProbeMap, ImportError> {
+		let mut header = BTreeMap::new();
+		let mut rows = Vec::new();
+		for line in contents.lines() {
+			let Some((key, value)) = line.split_once(':') else {
+				continue;
+			};
+			let key = key.trim().to_ascii_uppercase();
+			if key == "ROWDATA" {
+				rows.push(value.split_whitespace().collect::<Vec<&str>>());
+			} else {
+				header.insert(key, value.trim());
+			}
+		}
+
+		let pass_bins: BTreeSet<u32> = match header.get("BCEQU") {
+			Some(bins) => bins
+				.split_whitespace()
+				.filter_map(|bin| u32::from_str_radix(bin, 16).ok())
+				.collect(),
+			None => BTreeSet::from([1]),
+		};
+		let notch = match header.get("FNLOC").and_then(|angle| angle.parse::<u32>().ok()) {
+			Some(0) => Notch::Up,
+			Some(90) => Notch::Right,
+			Some(270) => Notch::Left,
+			_ => Notch::Down,
+		};
+
+		let mut dies = BTreeMap::new();
+		for (row, bins) in rows.iter().enumerate() {
+			for (column, bin) in bins.iter().enumerate() {
+				if EMPTY_BINS.contains(bin) {
+					continue;
+				}
+				let bin = u32::from_str_radix(bin, 16)
+					.map_err(|_| ImportError::Format(format!("Invalid bin {} in row {}", bin, row + 1)))?;
+				dies.insert((column as i32, row as i32), (bin, pass_bins.contains(&bin)));
+			}
+		}
+		if dies.is_empty() {
+			return Err(ImportError::Format(String::from("No dies in map")));
+		}
+
+		let column = dies.keys().map(|(column, _)| *column).min().unwrap_or(0);
+		let row = dies.keys().map(|(_, row)| *row).min().unwrap_or(0);
+		let dies = dies
+			.into_iter()
+			.map(|((x, y), die)| ((x - column, y - row), die))
+			.collect();
+
+		Ok(ProbeMap {
+			name: String::from(name),
+			dies,
+			convention: Some(ProberConvention {
+				origin: IndexOrigin::Corner,
+				notch,
+				x_right: true,
+				y_up: false,
+			}),
+		})
+	}
+
+	pub fn load(path: &Path) -> Result<ProbeMap, ImportError> {
+		let name = path
+			.file_name()
+			.map_or_else(String::new, |name| name.to_string_lossy().to_string());
+		ProbeMap::parse(&name, &fs::read_to_string(path)?)
+	}
+
+	pub fn measure(&self, wafer: &Wafer, map: &WaferMap, convention: ProberConvention) -> Vec<Measurement> {
+		let convention = self.convention.unwrap_or(convention);
+		let indices = prober_indices(wafer, map, convention);
+		let (column_origin, row_origin) = match convention.origin {
+			IndexOrigin::Corner => {
+				let complete = || {
+					indices
+						.iter()
+						.filter(|&&(x, y, _, _)| map.sites[x][y].die_type == DieType::Complete)
+				};
+				(
+					complete().map(|index| index.2).min().unwrap_or(0),
+					complete().map(|index| index.3).min().unwrap_or(0),
+				)
+			}
+			IndexOrigin::Center => (0, 0),
+		};
+		indices
+			.into_iter()
+			.filter_map(|(x, y, column, row)| {
+				self.dies
+					.get(&(column - column_origin, row - row_origin))
+					.map(|&(bin, pass)| Measurement {
+						column: x,
+						row: y,
+						bin,
+						pass,
+					})
+			})
+			.collect()
+	}
+
+	pub fn good(&self) -> usize {
+		self.dies.values().filter(|(_, pass)| *pass).count()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn parse_csv() {
+		let probe = ProbeMap::parse("lot.csv", "x,y,bin,pass\n0,0,1\n1,0,7,pass\n-1,2,1,0\n\n2,3,3,\n").unwrap();
+		assert_eq!(probe.convention, None);
+		assert_eq!(
+			probe.dies,
+			BTreeMap::from([
+				((0, 0), (1, true)),
+				((1, 0), (7, true)),
+				((-1, 2), (1, false)),
+				((2, 3), (3, false)),
+			])
+		);
+		assert_eq!(probe.good(), 2);
+		assert!(ProbeMap::parse("lot.csv", "x,y,bin\n0,0,1\n1,zero,1\n").is_err());
+	}
+
+	#[test]
+	fn parse_sinf() {
+		let sinf = "DEVICE:TEST\nFNLOC:90\nBCEQU:01 1A\nROWCT:3\nCOLCT:4\n\
+			RowData:__ __ 01 __\nRowData:__ 1A 02 01\nRowData:__ @@ FF 01\n";
+		let probe = ProbeMap::parse("lot.sinf", sinf).unwrap();
+		assert_eq!(
+			probe.dies,
+			BTreeMap::from([
+				((1, 0), (0x01, true)),
+				((0, 1), (0x1A, true)),
+				((1, 1), (0x02, false)),
+				((2, 1), (0x01, true)),
+				((1, 2), (0xFF, false)),
+				((2, 2), (0x01, true)),
+			])
+		);
+		let convention = probe.convention.unwrap();
+		assert_eq!(convention.origin, IndexOrigin::Corner);
+		assert_eq!(convention.notch, Notch::Right);
+		assert!(ProbeMap::parse("lot.sinf", "RowData:01 ZZ\n").is_err());
+		assert!(ProbeMap::parse("lot.sinf", "RowData:__ __\n").is_err());
+	}
+
+	#[test]
+	fn measure_sinf() {
+		let wafer = Wafer {
+			translation: (3.0, 3.0),
+			..Wafer::default()
+		};
+		let map = WaferMap::simulate(&wafer);
+		let convention = ProberConvention {
+			origin: IndexOrigin::Corner,
+			notch: Notch::Down,
+			x_right: true,
+			y_up: false,
+		};
+
+		let complete: Vec<(usize, usize, i32, i32)> = prober_indices(&wafer, &map, convention)
+			.into_iter()
+			.filter(|&(x, y, _, _)| map.sites[x][y].die_type == DieType::Complete)
+			.collect();
+		let column_origin = complete.iter().map(|index| index.2).min().unwrap();
+		let row_origin = complete.iter().map(|index| index.3).min().unwrap();
+		let complete: Vec<(usize, usize, i32, i32)> = complete
+			.into_iter()
+			.map(|(x, y, column, row)| (x, y, column - column_origin, row - row_origin))
+			.collect();
+		let columns = complete.iter().map(|index| index.2).max().unwrap() + 1;
+		let rows = complete.iter().map(|index| index.3).max().unwrap() + 1;
+		let mut grid = vec![vec!["__"; columns as usize]; rows as usize];
+		for &(x, y, column, row) in &complete {
+			grid[row as usize][column as usize] = if map.sites[x][y].is_good() { "01" } else { "02" };
+		}
+		let sinf: String = grid.iter().map(|row| format!("RowData:{}\n", row.join(" "))).collect();
+
+		let probe = ProbeMap::parse("wafer.sinf", &sinf).unwrap();
+		let measured = probe.measure(&wafer, &map, ProberConvention::default());
+		assert_eq!(measured.len(), complete.len());
+		for measurement in &measured {
+			let site = &map.sites[measurement.column][measurement.row];
+			assert_eq!(site.die_type, DieType::Complete);
+			assert_eq!(measurement.pass, site.is_good());
+		}
+	}
+}
@@ -0,0 +1,257 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+use std::fmt::{Display, Formatter};
+use std::path::Path;
+use std::{fmt, fs, io};
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+use crate::file::ProberConvention;
+use crate::interposer::{Interposer, MAXIMUM_CHIPLET_COUNT, MAXIMUM_RETICLE_MULTIPLE};
+use crate::partition::{Partition, MAXIMUM_CHIPLETS};
+use crate::scenario::Scenario;
+use crate::stacking::{Stack, MAXIMUM_TIERS};
+use crate::wafer::{Shuttle, MAXIMUM_DESIGN_COPIES, MINIMUM_DIE_DIMENSION};
+
+pub const PROJECT_VERSION: u64 = 2;
+pub const PROJECT_EXTENSION: &str = "json";
+
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct Project {
+	pub version: u64,
+	pub scenarios: Vec<Scenario>,
+	pub active: usize,
+	pub partition: Partition,
+	pub stack: Stack,
+	pub interposer: Interposer,
+	pub shuttle: Shuttle,
+	pub convention: ProberConvention,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum ProjectError {
+	Io(String),
+	Format(String),
+	Version(u64),
+}
+
+impl Project {
+	pub fn to_json(&self) -> Result<String, ProjectError> {
+		Ok(serde_json::to_string_pretty(self)?)
+	}
+
+	pub fn from_json(json: &str) -> Result<Project, ProjectError> {
+		let mut value: Value = serde_json::from_str(json)?;
+		let version = value.get("version").and_then(Value::as_u64).unwrap_or(0);
+		if version > PROJECT_VERSION {
+			return Err(ProjectError::Version(version));
+		}
+		migrate(&mut value, version);
+
+		let mut project: Project = serde_json::from_value(value)?;
+		project.version = PROJECT_VERSION;
+		if project.scenarios.is_empty() {
+			project.scenarios.push(Scenario::default());
+		}
+		project.active = project.active.min(project.scenarios.len() - 1);
+		project.clamp();
+		Ok(project)
+	}
+
+	fn clamp(&mut self) {
+		for scenario in &mut self.scenarios {
+			scenario.wafer.clamp(scenario.reticle_limit);
+			if scenario.simple_critical_area {
+				scenario.wafer.critical_area = scenario.wafer.usable_area();
+			}
+		}
+
+		self.partition.max_chiplets = self.partition.max_chiplets.clamp(1, MAXIMUM_CHIPLETS);
+		self.partition.overhead = self.partition.overhead.clamp(0.0, 100.0);
+		self.partition.assembly_yield = self.partition.assembly_yield.clamp(0.0, 100.0);
+
+		self.stack.tiers = self.stack.tiers.clamp(1, MAXIMUM_TIERS);
+		self.stack.test_coverage = self.stack.test_coverage.clamp(0.0, 100.0);
+		self.stack.bond_yield = self.stack.bond_yield.clamp(0.0, 100.0);
+
+		let interposer = &mut self.interposer;
+		interposer.reticles = (
+			interposer.reticles.0.clamp(0.1, MAXIMUM_RETICLE_MULTIPLE),
+			interposer.reticles.1.clamp(0.1, MAXIMUM_RETICLE_MULTIPLE),
+		);
+		interposer.interposer_yield = interposer.interposer_yield.clamp(0.0, 100.0);
+		interposer.assembly_yield = interposer.assembly_yield.clamp(0.0, 100.0);
+		for chiplet in &mut interposer.chiplets {
+			chiplet.count = chiplet.count.clamp(1, MAXIMUM_CHIPLET_COUNT);
+			chiplet.die_yield = chiplet.die_yield.clamp(0.0, 100.0);
+			chiplet.cost = chiplet.cost.max(0.0);
+		}
+
+		let long = self.scenarios[self.active].wafer.reticle.long();
+		for design in &mut self.shuttle.designs {
+			design.width = design.width.clamp(MINIMUM_DIE_DIMENSION, long);
+			design.height = design.height.clamp(MINIMUM_DIE_DIMENSION, long);
+			design.copies = design.copies.min(MAXIMUM_DESIGN_COPIES);
+		}
+	}
+
+	pub fn save(&self, path: &Path) -> Result<(), ProjectError> {
+		fs::write(path, self.to_json()?)?;
+		Ok(())
+	}
+
+	pub fn load(path: &Path) -> Result<Project, ProjectError> {
+		Project::from_json(&fs::read_to_string(path)?)
+	}
+}
+
+fn migrate(value: &mut Value, version: u64) {
+	let Some(project) = value.as_object_mut() else {
+		return;
+	};
+
+	if version < 2 {
+		let mut scenario = Map::new();
+		scenario.insert(String::from("name"), Value::from("Scenario 1"));
+		for key in ["wafer", "reticle_limit", "simple_critical_area", "scribe_equal"] {
+			if let Some(field) = project.remove(key) {
+				scenario.insert(String::from(key), field);
+			}
+		}
+		project.insert(String::from("scenarios"), Value::Array(vec![Value::Object(scenario)]));
+	}
+}
+
+impl Default for Project {
+	fn default() -> Project {
+		Project {
+			version: PROJECT_VERSION,
+			scenarios: vec![Scenario::default()],
+			active: 0,
+			partition: Partition::default(),
+			stack: Stack::default(),
+			interposer: Interposer::default(),
+			shuttle: Shuttle::default(),
+			convention: ProberConvention::default(),
+		}
+	}
+}
+
+impl Display for ProjectError {
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		match self {
+			ProjectError::Io(error) => write!(f, "Could not access file: {}", error),
+			ProjectError::Format(error) => write!(f, "Invalid project file: {}", error),
+			ProjectError::Version(version) => write!(
+				f,
+				"Project file version {} is newer than supported version {}",
+				version, PROJECT_VERSION
+			),
+		}
+	}
+}
+
+impl From<io::Error> for ProjectError {
+	fn from(error: io::Error) -> ProjectError {
+		ProjectError::Io(error.to_string())
+	}
+}
+
+impl From<serde_json::Error> for ProjectError {
+	fn from(error: serde_json::Error) -> ProjectError {
+		ProjectError::Format(error.to_string())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::die::DieType;
+	use crate::wafer::{Stagger, MAXIMUM_SCRIBE_WIDTH, MAXIMUM_STAGGER};
+
+	#[test]
+	fn round_trips_current_version() {
+		let mut project = Project::default();
+		project.scenarios[0].wafer.stagger = Stagger::PerRow(vec![0.0, 0.25]);
+		project.scenarios.push(Scenario {
+			name: String::from("Scenario 2"),
+			scribe_equal: true,
+			..Scenario::default()
+		});
+		project.active = 1;
+		project.stack.tiers = 4;
+
+		let loaded = Project::from_json(&project.to_json().unwrap()).unwrap();
+		assert_eq!(loaded, project);
+	}
+
+	#[test]
+	fn migrates_single_wafer_files() {
+		let json = r#"{"version": 1, "wafer": {"defect_rate": 0.2}, "scribe_equal": true, "reticle_limit": false}"#;
+		let project = Project::from_json(json).unwrap();
+		assert_eq!(project.version, PROJECT_VERSION);
+		assert_eq!(project.scenarios.len(), 1);
+		assert_eq!(project.active, 0);
+
+		let scenario = &project.scenarios[0];
+		assert_eq!(scenario.name, "Scenario 1");
+		assert_eq!(scenario.wafer.defect_rate, 0.2);
+		assert!(scenario.scribe_equal);
+		assert!(!scenario.reticle_limit);
+		assert!(scenario.simple_critical_area);
+	}
+
+	#[test]
+	fn rejects_newer_versions() {
+		let json = format!(r#"{{"version": {}}}"#, PROJECT_VERSION + 1);
+		assert_eq!(
+			Project::from_json(&json),
+			Err(ProjectError::Version(PROJECT_VERSION + 1))
+		);
+		assert!(matches!(Project::from_json("[1, 2"), Err(ProjectError::Format(_))));
+	}
+
+	#[test]
+	fn clamps_out_of_range_values() {
+		let json = r#"{
+			"version": 2,
+			"scenarios": [{
+				"wafer": {
+					"die": {"size": {"Rectangle": {"width": 0.0, "height": -3.0}}, "seal_ring": 0.0, "keep_out": 0.0},
+					"scribe_lanes": [-1.0, 100.0],
+					"field_scribe_lanes": [-5.0, -5.0],
+					"edge_loss": -10.0,
+					"cluster": 0.0,
+					"stagger": {"PerRow": [-1.0, 3.0]}
+				}
+			}],
+			"active": 7,
+			"stack": {"tiers": 4000000000, "test_coverage": 150.0, "bond_yield": -1.0}
+		}"#;
+		let project = Project::from_json(json).unwrap();
+		let wafer = &project.scenarios[0].wafer;
+		assert_eq!(wafer.die.width(), MINIMUM_DIE_DIMENSION);
+		assert_eq!(wafer.die.height(), MINIMUM_DIE_DIMENSION);
+		assert_eq!(wafer.scribe_lanes, (0.0, MAXIMUM_SCRIBE_WIDTH));
+		assert_eq!(wafer.field_scribe_lanes, (0.0, 0.0));
+		assert_eq!(wafer.edge_loss, 0.0);
+		assert!(wafer.cluster > 0.0);
+		assert_eq!(wafer.stagger, Stagger::PerRow(vec![0.0, MAXIMUM_STAGGER]));
+		assert_eq!(project.active, 0);
+		assert_eq!(project.stack.tiers, MAXIMUM_TIERS);
+		assert_eq!(project.stack.test_coverage, 100.0);
+		assert_eq!(project.stack.bond_yield, 0.0);
+
+		let dies = wafer.get_dies();
+		assert!(dies
+			.iter()
+			.flatten()
+			.any(|(die_type, _)| *die_type == DieType::Complete));
+	}
+}
@@ -0,0 +1,305 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+use std::fmt::{Display, Formatter, Write};
+use std::ops::Neg;
+use std::path::Path;
+use std::{fmt, fs};
+
+use serde::{Deserialize, Serialize};
+
+use crate::die::DieType;
+use crate::file::ExportError;
+use crate::wafer::{Wafer, WaferMap};
+
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Deserialize, Serialize)]
+pub enum IndexOrigin {
+	#[default]
+	Center,
+	Corner,
+}
+
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Deserialize, Serialize)]
+pub enum Notch {
+	#[default]
+	Down,
+	Left,
+	Up,
+	Right,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct ProberConvention {
+	pub origin: IndexOrigin,
+	pub notch: Notch,
+	pub x_right: bool,
+	pub y_up: bool,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum SiteFormat {
+	Csv,
+	Json,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize)]
+pub struct SiteRecord {
+	pub column: i32,
+	pub row: i32,
+	pub lower_left_x: f32,
+	pub lower_left_y: f32,
+	pub center_x: f32,
+	pub center_y: f32,
+	pub die_type: DieType,
+	pub expected_yield: f32,
+	pub pass: bool,
+}
+
+impl IndexOrigin {
+	pub const ALL: &'static [IndexOrigin] = &[IndexOrigin::Center, IndexOrigin::Corner];
+}
+
+impl Notch {
+	pub const ALL: &'static [Notch] = &[Notch::Down, Notch::Left, Notch::Up, Notch::Right];
+}
+
+impl ProberConvention {
+	pub fn orient<T: Neg<Output = T>>(&self, x: T, y: T) -> (T, T) {
+		let (x, y) = self.rotate(x, y);
+		self.flip(x, y)
+	}
+
+	fn flip<T: Neg<Output = T>>(&self, x: T, y: T) -> (T, T) {
+		(if self.x_right { x } else { -x }, if self.y_up { y } else { -y })
+	}
+
+	fn rotate<T: Neg<Output = T>>(&self, x: T, y: T) -> (T, T) {
+		match self.notch {
+			Notch::Down => (x, y),
+			Notch::Left => (y, -x),
+			Notch::Up => (-x, -y),
+			Notch::Right => (-y, x),
+		}
+	}
+
+	fn is_transposed(&self) -> bool {
+		matches!(self.notch, Notch::Left | Notch::Right)
+	}
+}
+
+impl SiteFormat {
+	pub fn extension(self) -> &'static str {
+		match self {
+			SiteFormat::Csv => "csv",
+			SiteFormat::Json => "json",
+		}
+	}
+}
+
+pub fn prober_indices(wafer: &Wafer, map: &WaferMap, convention: ProberConvention) -> Vec<(usize, usize, i32, i32)> {
+	let mut indices: Vec<(usize, usize, i32, i32)> = map
+		.sites
+		.iter()
+		.enumerate()
+		.flat_map(|(x, column)| column.iter().enumerate().map(move |(y, site)| (x, y, site)))
+		.filter(|(_, _, site)| site.die_type != DieType::None)
+		.map(|(x, y, _)| {
+			let (column, row) = wafer.site_index(x, y);
+			let (column, row) = convention.orient(column, -row);
+			(x, y, column, row)
+		})
+		.collect();
+
+	if convention.origin == IndexOrigin::Corner {
+		let column = indices.iter().map(|index| index.2).min().unwrap_or(0);
+		let row = indices.iter().map(|index| index.3).min().unwrap_or(0);
+		for index in &mut indices {
+			index.2 -= column;
+			index.3 -= row;
+		}
+	}
+	indices
+}
+
+pub fn site_records(wafer: &Wafer, map: &WaferMap, convention: ProberConvention) -> Vec<SiteRecord> {
+	let center = (wafer.shape.max_width() / 2.0, wafer.shape.max_height() / 2.0);
+	let (width, height) = if convention.is_transposed() {
+		(wafer.die.height(), wafer.die.width())
+	} else {
+		(wafer.die.width(), wafer.die.height())
+	};
+	let die_yield = wafer.die_yield();
+
+	prober_indices(wafer, map, convention)
+		.into_iter()
+		.map(|(x, y, column, row)| {
+			let site = &map.sites[x][y];
+			let site_center = (
+				site.coord.x + wafer.die.width() / 2.0 - center.0,
+				center.1 - site.coord.y - wafer.die.height() / 2.0,
+			);
+			let (center_x, center_y) = convention.orient(site_center.0, site_center.1);
+			let (lower_left_x, lower_left_y) = (center_x - width / 2.0, center_y - height / 2.0);
+
+			SiteRecord {
+				column,
+				row,
+				lower_left_x,
+				lower_left_y,
+				center_x,
+				center_y,
+				die_type: site.die_type,
+				expected_yield: if site.die_type == DieType::Complete {
+					die_yield
+				} else {
+					0.0
+				},
+				pass: site.is_good(),
+			}
+		})
+		.collect()
+}
+
+pub fn sites_csv(records: &[SiteRecord]) -> String {
+	let mut csv = String::from("column,row,lower_left_x,lower_left_y,center_x,center_y,die_type,expected_yield,pass\n");
+	for record in records {
+		let _ = writeln!(
+			csv,
+			"{},{},{:.4},{:.4},{:.4},{:.4},{:?},{:.6},{}",
+			record.column,
+			record.row,
+			record.lower_left_x,
+			record.lower_left_y,
+			record.center_x,
+			record.center_y,
+			record.die_type,
+			record.expected_yield,
+			record.pass
+		);
+	}
+	csv
+}
+
+pub fn export_sites(
+	path: &Path, format: SiteFormat, wafer: &Wafer, map: &WaferMap, convention: ProberConvention,
+) -> Result<(), ExportError> {
+	let records = site_records(wafer, map, convention);
+	let contents = match format {
+		SiteFormat::Csv => sites_csv(&records),
+		SiteFormat::Json => serde_json::to_string_pretty(&records)?,
+	};
+	fs::write(path, contents)?;
+	Ok(())
+}
+
+impl Default for ProberConvention {
+	fn default() -> ProberConvention {
+		ProberConvention {
+			origin: IndexOrigin::default(),
+			notch: Notch::default(),
+			x_right: true,
+			y_up: true,
+		}
+	}
+}
+
+impl Display for IndexOrigin {
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		match self {
+			IndexOrigin::Center => f.write_str("Center Die"),
+			IndexOrigin::Corner => f.write_str("Corner Die"),
+		}
+	}
+}
+
+impl Display for Notch {
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		match self {
+			Notch::Down => f.write_str("Notch Down"),
+			Notch::Left => f.write_str("Notch Left"),
+			Notch::Up => f.write_str("Notch Up"),
+			Notch::Right => f.write_str("Notch Right"),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn conventions() -> Vec<ProberConvention> {
+		Notch::ALL
+			.iter()
+			.flat_map(|&notch| {
+				[(true, true), (true, false), (false, true), (false, false)].map(|(x_right, y_up)| ProberConvention {
+					origin: IndexOrigin::Center,
+					notch,
+					x_right,
+					y_up,
+				})
+			})
+			.collect()
+	}
+
+	fn record(records: &[SiteRecord], indices: &[(usize, usize, i32, i32)], x: usize, y: usize) -> SiteRecord {
+		let i = indices.iter().position(|index| (index.0, index.1) == (x, y)).unwrap();
+		records[i]
+	}
+
+	#[test]
+	fn lower_left_is_below_and_left_of_center() {
+		let mut wafer = Wafer::default();
+		wafer.die = wafer.die.new_width(10.0).new_height(6.0);
+		let map = WaferMap::simulate(&wafer);
+		for convention in conventions() {
+			for record in site_records(&wafer, &map, convention) {
+				assert!(record.lower_left_x < record.center_x, "{:?} {:?}", convention, record);
+				assert!(record.lower_left_y < record.center_y, "{:?} {:?}", convention, record);
+				let size = (
+					2.0 * (record.center_x - record.lower_left_x),
+					2.0 * (record.center_y - record.lower_left_y),
+				);
+				let expected = if convention.is_transposed() {
+					(6.0, 10.0)
+				} else {
+					(10.0, 6.0)
+				};
+				assert!(
+					(size.0 - expected.0).abs() < 1e-3 && (size.1 - expected.1).abs() < 1e-3,
+					"{:?}",
+					size
+				);
+			}
+		}
+	}
+
+	#[test]
+	fn indices_follow_coordinates() {
+		let step = |from: &SiteRecord, to: &SiteRecord| {
+			let sign = |delta: f32| if delta.abs() < 1e-3 { 0 } else { delta.signum() as i32 };
+			(sign(to.center_x - from.center_x), sign(to.center_y - from.center_y))
+		};
+		let wafer = Wafer::default();
+		let map = WaferMap::simulate(&wafer);
+		let (x, y) = (map.sites.len() / 2, map.sites[0].len() / 2);
+		for convention in conventions() {
+			let records = site_records(&wafer, &map, convention);
+			let indices = prober_indices(&wafer, &map, convention);
+			let site = record(&records, &indices, x, y);
+			let right = record(&records, &indices, x + 1, y);
+			let below = record(&records, &indices, x, y + 1);
+
+			let expected = convention.orient(1, 0);
+			assert_eq!((right.column - site.column, right.row - site.row), expected);
+			assert_eq!(step(&site, &right), expected);
+
+			let expected = convention.orient(0, -1);
+			assert_eq!((below.column - site.column, below.row - site.row), expected);
+			assert_eq!(step(&site, &below), expected);
+		}
+	}
+}
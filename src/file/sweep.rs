@@ -0,0 +1,30 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+use std::fmt::Write;
+use std::fs;
+use std::path::Path;
+
+use crate::analysis::SweepResult;
+use crate::file::ExportError;
+
+pub const SWEEP_EXTENSION: &str = "csv";
+
+pub fn sweep_csv(result: &SweepResult) -> String {
+	let (x_label, y_label) = result.parameters.labels();
+	let mut csv = format!("{},{},Good Dies\n", x_label, y_label);
+	for (x, column) in result.x.iter().zip(&result.good) {
+		for (y, good) in result.y.iter().zip(column) {
+			let _ = writeln!(csv, "{:.4},{:.4},{:.1}", x, y, good);
+		}
+	}
+	csv
+}
+
+pub fn export_sweep(path: &Path, result: &SweepResult) -> Result<(), ExportError> {
+	fs::write(path, sweep_csv(result))?;
+	Ok(())
+}
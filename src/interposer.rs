@@ -0,0 +1,126 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+use serde::{Deserialize, Serialize};
+
+use crate::die::{Die, DieSize, DieType};
+use crate::wafer::Wafer;
+
+pub const MAXIMUM_RETICLE_MULTIPLE: f32 = 4.0;
+pub const MAXIMUM_CHIPLET_COUNT: u32 = 16;
+
+#[derive(Clone, Copy, Debug, PartialEq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct Chiplet {
+	pub count: u32,
+	pub die_yield: f32,
+	pub cost: f32,
+}
+
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct Interposer {
+	pub reticles: (f32, f32),
+	pub interposer_yield: f32,
+	pub assembly_yield: f32,
+	pub chiplets: Vec<Chiplet>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct InterposerResult {
+	pub width: f32,
+	pub height: f32,
+	pub per_wafer: usize,
+	pub good_per_wafer: f32,
+	pub interposer_cost: f32,
+	pub package_yield: f32,
+	pub package_cost: f32,
+}
+
+impl Interposer {
+	pub fn wafer(&self, wafer: &Wafer) -> Wafer {
+		let (width, height) = wafer.reticle.dimensions();
+		let mut interposer = wafer.clone();
+		interposer.die = Die {
+			size: DieSize::Rectangle {
+				width: width * self.reticles.0,
+				height: height * self.reticles.1,
+			},
+			..Die::default()
+		};
+		interposer.stitching.enabled = true;
+		interposer.reticle_defects.clear();
+		interposer
+	}
+
+	pub fn evaluate(&self, wafer: &Wafer) -> InterposerResult {
+		let interposer = self.wafer(wafer);
+		let per_wafer = interposer
+			.get_dies()
+			.iter()
+			.flatten()
+			.filter(|(die_type, _)| *die_type == DieType::Complete)
+			.count();
+
+		let good_per_wafer = per_wafer as f32 * self.interposer_yield / 100.0 * interposer.stitch_yield();
+		let interposer_cost = interposer.cost.breakdown(per_wafer as f32, good_per_wafer).good_die;
+
+		let attached: u32 = self.chiplets.iter().map(|chiplet| chiplet.count).sum();
+		let package_yield = self
+			.chiplets
+			.iter()
+			.map(|chiplet| (chiplet.die_yield / 100.0).powi(chiplet.count as i32))
+			.product::<f32>()
+			* (self.assembly_yield / 100.0).powi(attached as i32);
+		let chiplet_cost: f32 = self
+			.chiplets
+			.iter()
+			.map(|chiplet| chiplet.count as f32 * chiplet.cost)
+			.sum();
+
+		InterposerResult {
+			width: interposer.die.width(),
+			height: interposer.die.height(),
+			per_wafer,
+			good_per_wafer,
+			interposer_cost,
+			package_yield,
+			package_cost: (interposer_cost + chiplet_cost + wafer.cost.packaging) / package_yield,
+		}
+	}
+}
+
+impl Default for Chiplet {
+	fn default() -> Chiplet {
+		Chiplet {
+			count: 1,
+			die_yield: 99.0,
+			cost: 100.0,
+		}
+	}
+}
+
+impl Default for Interposer {
+	fn default() -> Interposer {
+		Interposer {
+			reticles: (2.0, 1.5),
+			interposer_yield: 98.0,
+			assembly_yield: 99.5,
+			chiplets: vec![
+				Chiplet {
+					count: 2,
+					die_yield: 99.0,
+					cost: 400.0,
+				},
+				Chiplet {
+					count: 6,
+					die_yield: 98.0,
+					cost: 120.0,
+				},
+			],
+		}
+	}
+}
@@ -4,16 +4,36 @@
  * file, You can obtain one at http://mozilla.org/MPL/2.0/.
  */
 
+use std::{env, process};
+
 use iced::{Application, Settings};
 
 use crate::view::Calculator;
 
+pub mod analysis;
+pub mod cli;
+pub mod cost;
 pub mod die;
+pub mod file;
+pub mod interposer;
+pub mod partition;
+pub mod render;
+pub mod scenario;
+pub mod stacking;
 pub mod util;
 pub mod view;
 pub mod wafer;
 
 fn main() -> iced::Result {
+	let args: Vec<String> = env::args().skip(1).collect();
+	if !args.is_empty() {
+		if let Err(error) = cli::run(&args) {
+			eprintln!("{}", error);
+			process::exit(1);
+		}
+		return Ok(());
+	}
+
 	Calculator::run(Settings {
 		antialiasing: true,
 		..Settings::default()
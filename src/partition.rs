@@ -0,0 +1,145 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+use serde::{Deserialize, Serialize};
+
+use crate::die::{DieSize, DieType};
+use crate::wafer::Wafer;
+
+pub const MAXIMUM_CHIPLETS: u32 = 16;
+
+#[derive(Clone, Copy, Debug, PartialEq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct Partition {
+	pub max_chiplets: u32,
+	pub overhead: f32,
+	pub assembly_yield: f32,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Candidate {
+	pub chiplets: u32,
+	pub width: f32,
+	pub height: f32,
+	pub dies_per_wafer: usize,
+	pub die_yield: f32,
+	pub cost_per_good_die: f32,
+	pub package_yield: f32,
+	pub cost_per_package: f32,
+}
+
+impl Partition {
+	pub fn chiplet(&self, wafer: &Wafer, chiplets: u32) -> Wafer {
+		let overhead = if chiplets > 1 { 1.0 + self.overhead / 100.0 } else { 1.0 };
+		let scale = (overhead / chiplets as f32).sqrt();
+
+		let mut chiplet = wafer.clone();
+		chiplet.die.size = DieSize::Rectangle {
+			width: wafer.die.active_width() * scale,
+			height: wafer.die.active_height() * scale,
+		};
+		chiplet.critical_area = wafer.critical_area * scale * scale;
+		chiplet.clamp_reticle_defects();
+		chiplet
+	}
+
+	pub fn candidate(&self, wafer: &Wafer, chiplets: u32) -> Candidate {
+		let chiplet = self.chiplet(wafer, chiplets);
+		let dies_per_wafer = chiplet
+			.get_dies()
+			.iter()
+			.flatten()
+			.filter(|(die_type, _)| *die_type == DieType::Complete)
+			.count();
+		let die_yield = chiplet.die_yield();
+
+		let cost = chiplet
+			.cost
+			.breakdown(dies_per_wafer as f32, dies_per_wafer as f32 * die_yield);
+		let package_yield = (self.assembly_yield / 100.0).powi(chiplets as i32);
+
+		Candidate {
+			chiplets,
+			width: chiplet.die.active_width(),
+			height: chiplet.die.active_height(),
+			dies_per_wafer,
+			die_yield,
+			cost_per_good_die: cost.good_die,
+			package_yield,
+			cost_per_package: (chiplets as f32 * cost.good_die + chiplet.cost.packaging) / package_yield,
+		}
+	}
+
+	pub fn candidates(&self, wafer: &Wafer) -> Vec<Candidate> {
+		(1..=self.max_chiplets.max(1))
+			.map(|chiplets| self.candidate(wafer, chiplets))
+			.collect()
+	}
+}
+
+impl Default for Partition {
+	fn default() -> Partition {
+		Partition {
+			max_chiplets: 8,
+			overhead: 10.0,
+			assembly_yield: 99.0,
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn wafer() -> Wafer {
+		let mut wafer = Wafer::default();
+		wafer.die = wafer.die.new_width(20.0).new_height(10.0);
+		wafer.critical_area = 100.0;
+		wafer
+	}
+
+	#[test]
+	fn scales_chiplets() {
+		let partition = Partition::default();
+		let wafer = wafer();
+
+		let whole = partition.chiplet(&wafer, 1);
+		assert_eq!((whole.die.active_width(), whole.die.active_height()), (20.0, 10.0));
+		assert_eq!(whole.critical_area, 100.0);
+
+		let quarter = partition.chiplet(&wafer, 4);
+		assert!((quarter.die.active_area() - 200.0 * 1.1 / 4.0).abs() < 1e-3);
+		assert!((quarter.die.active_width() / quarter.die.active_height() - 2.0).abs() < 1e-4);
+		assert!((quarter.critical_area - 100.0 * 1.1 / 4.0).abs() < 1e-3);
+	}
+
+	#[test]
+	fn ranks_candidates() {
+		let partition = Partition {
+			assembly_yield: 90.0,
+			..Partition::default()
+		};
+		let wafer = wafer();
+		let candidates = partition.candidates(&wafer);
+		assert_eq!(candidates.len(), partition.max_chiplets as usize);
+		assert_eq!(
+			Partition {
+				max_chiplets: 0,
+				..partition
+			}
+			.candidates(&wafer)
+			.len(),
+			1
+		);
+
+		let (single, pair) = (candidates[0], candidates[1]);
+		assert!(pair.dies_per_wafer > single.dies_per_wafer);
+		assert!(pair.die_yield > single.die_yield);
+		assert!((pair.package_yield - 0.81).abs() < 1e-6);
+		let packaging = wafer.cost.packaging;
+		assert!((pair.cost_per_package - (2.0 * pair.cost_per_good_die + packaging) / 0.81).abs() < 1e-3);
+	}
+}
@@ -0,0 +1,328 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+pub use svg::Svg;
+
+use iced::{Color, Point, Size, Vector};
+
+use crate::cost::format_cost;
+use crate::die::DieType;
+use crate::wafer::{Fault, Shape, Wafer, WaferMap};
+
+mod svg;
+
+pub const FONT_SIZE: f32 = 16.0;
+const READOUT_COLUMNS: usize = 3;
+
+const GOOD: Color = Color::from_rgba(60.0 / 255.0, 180.0 / 255.0, 60.0 / 255.0, 0.8);
+const PARTIAL: Color = Color::from_rgba(200.0 / 255.0, 200.0 / 255.0, 0.0, 0.8);
+const WASTED: Color = Color::from_rgba(180.0 / 255.0, 60.0 / 255.0, 60.0 / 255.0, 0.8);
+const DEFECTIVE: (Color, Color) = (
+	Color::from_rgb(70.0 / 255.0, 70.0 / 255.0, 70.0 / 255.0),
+	Color::from_rgb(180.0 / 255.0, 180.0 / 255.0, 180.0 / 255.0),
+);
+const RETICLE_DEFECT: (Color, Color) = (
+	Color::from_rgb(90.0 / 255.0, 40.0 / 255.0, 120.0 / 255.0),
+	Color::from_rgb(200.0 / 255.0, 170.0 / 255.0, 230.0 / 255.0),
+);
+const MEASURED_FAIL: Color = Color::from_rgba(200.0 / 255.0, 40.0 / 255.0, 140.0 / 255.0, 0.8);
+const UNTESTED: Color = Color::from_rgba(200.0 / 255.0, 200.0 / 255.0, 200.0 / 255.0, 0.8);
+const DEFECT: (Color, Color) = (
+	Color::BLACK,
+	Color::from_rgb(120.0 / 255.0, 120.0 / 255.0, 120.0 / 255.0),
+);
+const SECONDARY: (Color, Color) = (
+	Color::from_rgba(60.0 / 255.0, 120.0 / 255.0, 220.0 / 255.0, 0.8),
+	Color::from_rgb(40.0 / 255.0, 60.0 / 255.0, 110.0 / 255.0),
+);
+
+pub trait Painter {
+	fn fill_rectangle(&mut self, top_left: Point, size: Size, color: Color);
+	fn stroke_rectangle(&mut self, top_left: Point, size: Size, color: Color, width: f32);
+	fn fill_circle(&mut self, center: Point, radius: f32, color: Color);
+	fn stroke_circle(&mut self, center: Point, radius: f32, color: Color, width: f32);
+	fn stroke_line(&mut self, from: Point, to: Point, color: Color, width: f32);
+	fn fill_text(&mut self, content: &str, position: Point, color: Color);
+}
+
+pub fn draw_wafer(painter: &mut impl Painter, bounds: Size, wafer: &Wafer, map: &WaferMap) {
+	let readouts = readouts(wafer, map);
+	let legend = legend(wafer, map);
+	let rows = readouts.len().div_ceil(READOUT_COLUMNS) + legend.len().div_ceil(READOUT_COLUMNS);
+
+	let height = 1.1 + 0.0625 * rows as f32;
+	let dimension = (bounds.width.min(bounds.height) * 0.8).min(bounds.height * 0.95 / height);
+	let offset = dimension * (0.05 + 0.03125 * (rows as f32 - 2.0));
+	let center = Point::new(bounds.width / 2.0, bounds.height / 2.0) - Vector::new(0.0, offset);
+	let top_left = center - Vector::new(dimension / 2.0, dimension / 2.0);
+
+	painter.stroke_rectangle(
+		top_left - Vector::new(dimension * 0.05, dimension * 0.05),
+		Size::new(dimension * 1.1, dimension * height),
+		Color::from_rgb8(120, 120, 120),
+		1.0,
+	);
+	painter.stroke_rectangle(
+		top_left - Vector::new(dimension * 0.025, dimension * 0.025),
+		Size::new(dimension * 1.05, dimension * 1.05),
+		Color::from_rgb8(170, 170, 170),
+		1.0,
+	);
+
+	let (scale, frame_top_left) = draw_outline(painter, wafer, center, dimension);
+
+	let die_size = Size::new(wafer.die.width() * scale, wafer.die.height() * scale);
+	let border = wafer.die.border() * scale;
+	let active_size = Size::new(wafer.die.active_width() * scale, wafer.die.active_height() * scale);
+	for site in map.sites() {
+		if site.die_type == DieType::None {
+			continue;
+		}
+
+		let tl = frame_top_left + Vector::new(site.coord.x, site.coord.y) * scale;
+		let active_tl = tl + Vector::new(border, border);
+		if border > 0.0 {
+			painter.fill_rectangle(tl, die_size, Color::from_rgba8(150, 150, 150, 0.5));
+		}
+
+		match (site.die_type, site.fault) {
+			(DieType::Complete, _) if !map.measured.is_empty() => {
+				painter.fill_rectangle(active_tl, active_size, UNTESTED)
+			}
+			(DieType::Complete, Some(fault)) => {
+				let (fill, mark) = match fault {
+					Fault::Random => DEFECTIVE,
+					Fault::Reticle => RETICLE_DEFECT,
+				};
+				let center = active_tl + Vector::new(active_size.width / 2.0, active_size.height / 2.0);
+				painter.fill_rectangle(active_tl, active_size, fill);
+				painter.fill_circle(center, active_size.width.min(active_size.height) / 5.0, mark);
+			}
+			(DieType::Complete, None) => painter.fill_rectangle(active_tl, active_size, GOOD),
+			(DieType::Partial, _) => painter.fill_rectangle(active_tl, active_size, PARTIAL),
+			(DieType::Wasted, _) => painter.fill_rectangle(active_tl, active_size, WASTED),
+			(DieType::None, _) => {}
+		}
+	}
+
+	for measurement in &map.measured {
+		let site = &map.sites[measurement.column][measurement.row];
+		let active_tl = frame_top_left + Vector::new(site.coord.x, site.coord.y) * scale + Vector::new(border, border);
+		let fill = if measurement.pass { GOOD } else { MEASURED_FAIL };
+		painter.fill_rectangle(active_tl, active_size, fill);
+	}
+
+	if let Some(inspection) = &map.inspection {
+		let largest = inspection
+			.defects
+			.iter()
+			.fold(f32::EPSILON, |largest, defect| largest.max(defect.size));
+		for defect in &inspection.defects {
+			let radius = (defect.size / 2.0 * scale).max(1.5 * (1.0 + defect.size / largest));
+			let color = if defect.site.is_some() { DEFECT.0 } else { DEFECT.1 };
+			painter.fill_circle(
+				frame_top_left + Vector::new(defect.coord.x, defect.coord.y) * scale,
+				radius,
+				color,
+			);
+		}
+	}
+
+	let secondary_size = Size::new(wafer.secondary.width * scale, wafer.secondary.height * scale);
+	for site in &map.secondary {
+		let tl = frame_top_left + Vector::new(site.coord.x, site.coord.y) * scale;
+		let fill = match site.fault {
+			Some(_) => SECONDARY.1,
+			None => SECONDARY.0,
+		};
+		painter.fill_rectangle(tl, secondary_size, fill);
+	}
+
+	if wafer.stitching.enabled {
+		let exposures = wafer.exposures();
+		if exposures.is_stitched() {
+			let (vertical, horizontal) = exposures.stitch_lines();
+			let keep_out = wafer.stitching.keep_out * scale;
+			let stitch_color = Color::from_rgb8(40, 40, 160);
+			let keep_out_fill = Color::from_rgba8(40, 40, 160, 0.25);
+
+			for site in map.sites() {
+				if site.die_type == DieType::None {
+					continue;
+				}
+				let tl = frame_top_left + Vector::new(site.coord.x, site.coord.y) * scale;
+				for x in &vertical {
+					let top = tl + Vector::new(x * scale, 0.0);
+					let bottom = top + Vector::new(0.0, die_size.height);
+					painter.fill_rectangle(
+						top - Vector::new(keep_out, 0.0),
+						Size::new(2.0 * keep_out, die_size.height),
+						keep_out_fill,
+					);
+					painter.stroke_line(top, bottom, stitch_color, 1.0);
+				}
+				for y in &horizontal {
+					let left = tl + Vector::new(0.0, y * scale);
+					let right = left + Vector::new(die_size.width, 0.0);
+					painter.fill_rectangle(
+						left - Vector::new(0.0, keep_out),
+						Size::new(die_size.width, 2.0 * keep_out),
+						keep_out_fill,
+					);
+					painter.stroke_line(left, right, stitch_color, 1.0);
+				}
+			}
+		}
+	}
+
+	let cell = |i: usize| {
+		let (column, row) = (i % READOUT_COLUMNS, i / READOUT_COLUMNS);
+		top_left
+			+ Vector::new(
+				dimension * (0.0125 + 0.35 * column as f32),
+				dimension * (1.05 + 0.065 * row as f32),
+			)
+	};
+	let readout_cells = readouts.len().div_ceil(READOUT_COLUMNS) * READOUT_COLUMNS;
+	for (i, readout) in readouts.iter().enumerate() {
+		painter.fill_text(readout, cell(i), Color::BLACK);
+	}
+	for (i, (label, color)) in legend.into_iter().enumerate() {
+		let position = cell(readout_cells + i);
+		painter.fill_rectangle(
+			position + Vector::new(0.0, 0.15 * FONT_SIZE),
+			Size::new(0.75 * FONT_SIZE, 0.75 * FONT_SIZE),
+			color,
+		);
+		painter.fill_text(label, position + Vector::new(FONT_SIZE, 0.0), Color::BLACK);
+	}
+}
+
+pub fn draw_outline(painter: &mut impl Painter, wafer: &Wafer, center: Point, dimension: f32) -> (f32, Point) {
+	let outer_color = Color::from_rgb8(0, 200, 0);
+	let inner_color = Color::from_rgb8(200, 0, 0);
+
+	let edge_loss = wafer.edge_loss;
+	match wafer.shape {
+		Shape::Wafer(diameter) => {
+			let diameter = diameter.diameter();
+			let inner_diameter = diameter - 2.0 * edge_loss;
+			let scale = dimension / diameter;
+
+			painter.stroke_circle(center, dimension / 2.0, outer_color, 1.5);
+			painter.stroke_circle(
+				center,
+				(dimension / 2.0) * (inner_diameter / diameter),
+				inner_color,
+				1.5,
+			);
+
+			(scale, center - Vector::new(dimension / 2.0, dimension / 2.0))
+		}
+		Shape::Panel(panel) => {
+			let (width, height) = panel.dimensions();
+			let (inner_width, inner_height) = (width - 2.0 * edge_loss, height - 2.0 * edge_loss);
+			let scale = dimension / width.max(height);
+
+			let frame_top_left = center - Vector::new(width, height) * (scale / 2.0);
+
+			painter.stroke_rectangle(
+				frame_top_left,
+				Size::new(width * scale, height * scale),
+				outer_color,
+				1.5,
+			);
+			painter.stroke_rectangle(
+				frame_top_left + Vector::new(edge_loss, edge_loss) * scale,
+				Size::new(inner_width * scale, inner_height * scale),
+				inner_color,
+				1.5,
+			);
+
+			(scale, frame_top_left)
+		}
+	}
+}
+
+pub fn readouts(wafer: &Wafer, map: &WaferMap) -> Vec<String> {
+	let complete = map.count(DieType::Complete);
+	let good = map.good();
+	let cost = wafer.cost.breakdown(complete as f32, good as f32);
+	let random = map.faults(Fault::Random);
+	let reticle = map.faults(Fault::Reticle);
+	let defective = random + reticle;
+
+	let mut readouts = vec![
+		format!("Good Dies {}", good),
+		format!("Defective Dies {}", defective),
+		format!("Maximum Dies {}", complete),
+		format!("Wasted Dies {}", map.count(DieType::Wasted)),
+		format!("Partial Dies {}", map.count(DieType::Partial)),
+		format!("Fab Yield {:.2}%", defective as f32 / complete as f32 * 100.0),
+		format!("Cost / Gross Die {}", format_cost(cost.gross_die)),
+		format!("Cost / Good Die {}", format_cost(cost.good_die)),
+		format!("Cost / Shipped Unit {}", format_cost(cost.shipped_unit)),
+	];
+	if wafer.secondary.enabled {
+		let secondary = map.secondary.len();
+		let secondary_good = map.secondary_good();
+		readouts.push(format!("Secondary Dies {}", secondary));
+		readouts.push(format!("Secondary Good {}", secondary_good));
+		readouts.push(format!(
+			"Secondary Yield {:.2}%",
+			secondary_good as f32 / secondary as f32 * 100.0
+		));
+	}
+	if !wafer.reticle_defects.is_empty() {
+		readouts.push(format!("Reticle Defect Dies {}", reticle));
+		readouts.push(format!(
+			"Reticle Yield Loss {:.2}%",
+			reticle as f32 / complete as f32 * 100.0
+		));
+	}
+	if !map.measured.is_empty() {
+		let tested = map.measured.len();
+		readouts.push(format!("Tested Dies {}", tested));
+		readouts.push(format!(
+			"Measured Yield {:.2}%",
+			map.measured_good() as f32 / tested as f32 * 100.0
+		));
+		readouts.push(format!("Predicted Yield {:.2}%", wafer.die_yield() * 100.0));
+	}
+	if let Some(inspection) = &map.inspection {
+		readouts.push(format!("Inspected Defects {}", inspection.defects.len()));
+		readouts.push(format!("Dies With Defects {}", inspection.hits.len()));
+		readouts.push(format!("Forecast Killed Dies {:.1}", inspection.killed));
+		readouts.push(format!(
+			"Forecast Yield {:.2}%",
+			inspection.forecast_good(map) / complete as f32 * 100.0
+		));
+	}
+	readouts
+}
+
+fn legend(wafer: &Wafer, map: &WaferMap) -> Vec<(&'static str, Color)> {
+	let mut legend = vec![
+		("Good", GOOD),
+		("Defective", DEFECTIVE.0),
+		("Partial", PARTIAL),
+		("Wasted", WASTED),
+	];
+	if !wafer.reticle_defects.is_empty() {
+		legend.push(("Reticle Defect", RETICLE_DEFECT.0));
+	}
+	if wafer.secondary.enabled {
+		legend.push(("Secondary", SECONDARY.0));
+	}
+	if map.inspection.is_some() {
+		legend.push(("Defect", DEFECT.0));
+	}
+	if !map.measured.is_empty() {
+		legend.push(("Measured Fail", MEASURED_FAIL));
+		legend.push(("Untested", UNTESTED));
+	}
+	legend
+}
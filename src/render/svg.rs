@@ -0,0 +1,130 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+use std::fmt::Write;
+
+use iced::{Color, Point, Size};
+
+use crate::render::{Painter, FONT_SIZE};
+
+pub struct Svg {
+	size: Size,
+	body: String,
+}
+
+impl Svg {
+	pub fn new(size: Size) -> Svg {
+		Svg {
+			size,
+			body: String::new(),
+		}
+	}
+
+	pub fn finish(self) -> String {
+		format!(
+			concat!(
+				"<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{0}\" height=\"{1}\" viewBox=\"0 0 {0} {1}\">\n",
+				"<rect width=\"{0}\" height=\"{1}\" fill=\"white\"/>\n",
+				"{2}</svg>\n",
+			),
+			self.size.width, self.size.height, self.body
+		)
+	}
+}
+
+impl Painter for Svg {
+	fn fill_rectangle(&mut self, top_left: Point, size: Size, color: Color) {
+		let _ = writeln!(
+			self.body,
+			"<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" {}/>",
+			top_left.x,
+			top_left.y,
+			size.width,
+			size.height,
+			fill(color)
+		);
+	}
+
+	fn stroke_rectangle(&mut self, top_left: Point, size: Size, color: Color, width: f32) {
+		let _ = writeln!(
+			self.body,
+			"<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" fill=\"none\" {}/>",
+			top_left.x,
+			top_left.y,
+			size.width,
+			size.height,
+			stroke(color, width)
+		);
+	}
+
+	fn fill_circle(&mut self, center: Point, radius: f32, color: Color) {
+		let _ = writeln!(
+			self.body,
+			"<circle cx=\"{}\" cy=\"{}\" r=\"{}\" {}/>",
+			center.x,
+			center.y,
+			radius,
+			fill(color)
+		);
+	}
+
+	fn stroke_circle(&mut self, center: Point, radius: f32, color: Color, width: f32) {
+		let _ = writeln!(
+			self.body,
+			"<circle cx=\"{}\" cy=\"{}\" r=\"{}\" fill=\"none\" {}/>",
+			center.x,
+			center.y,
+			radius,
+			stroke(color, width)
+		);
+	}
+
+	fn stroke_line(&mut self, from: Point, to: Point, color: Color, width: f32) {
+		let _ = writeln!(
+			self.body,
+			"<line x1=\"{}\" y1=\"{}\" x2=\"{}\" y2=\"{}\" {}/>",
+			from.x,
+			from.y,
+			to.x,
+			to.y,
+			stroke(color, width)
+		);
+	}
+
+	fn fill_text(&mut self, content: &str, position: Point, color: Color) {
+		let _ = writeln!(
+			self.body,
+			"<text x=\"{}\" y=\"{}\" font-family=\"sans-serif\" font-size=\"{}\" dominant-baseline=\"hanging\" {}>{}</text>",
+			position.x,
+			position.y,
+			FONT_SIZE,
+			fill(color),
+			escape(content)
+		);
+	}
+}
+
+fn rgb(color: Color) -> String {
+	let [r, g, b, _] = color.into_rgba8();
+	format!("rgb({},{},{})", r, g, b)
+}
+
+fn fill(color: Color) -> String {
+	format!("fill=\"{}\" fill-opacity=\"{}\"", rgb(color), color.a)
+}
+
+fn stroke(color: Color, width: f32) -> String {
+	format!(
+		"stroke=\"{}\" stroke-opacity=\"{}\" stroke-width=\"{}\"",
+		rgb(color),
+		color.a,
+		width
+	)
+}
+
+fn escape(content: &str) -> String {
+	content.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;")
+}
@@ -0,0 +1,59 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+use serde::{Deserialize, Serialize};
+
+use crate::die::DieType;
+use crate::wafer::{Fault, Wafer, WaferMap};
+
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct Scenario {
+	pub name: String,
+	pub wafer: Wafer,
+	pub reticle_limit: bool,
+	pub simple_critical_area: bool,
+	pub scribe_equal: bool,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Summary {
+	pub complete: usize,
+	pub partial: usize,
+	pub wasted: usize,
+	pub defective: usize,
+	pub good: usize,
+	pub die_yield: f32,
+	pub cost_per_good_die: f32,
+}
+
+impl Summary {
+	pub fn new(wafer: &Wafer, map: &WaferMap) -> Summary {
+		let complete = map.count(DieType::Complete);
+		let good = map.good();
+		Summary {
+			complete,
+			partial: map.count(DieType::Partial),
+			wasted: map.count(DieType::Wasted),
+			defective: map.faults(Fault::Random) + map.faults(Fault::Reticle),
+			good,
+			die_yield: wafer.die_yield(),
+			cost_per_good_die: wafer.cost.breakdown(complete as f32, good as f32).good_die,
+		}
+	}
+}
+
+impl Default for Scenario {
+	fn default() -> Scenario {
+		Scenario {
+			name: String::from("Scenario 1"),
+			wafer: Wafer::default(),
+			reticle_limit: true,
+			simple_critical_area: true,
+			scribe_equal: false,
+		}
+	}
+}
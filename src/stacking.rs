@@ -0,0 +1,152 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+use serde::{Deserialize, Serialize};
+
+use crate::die::DieType;
+use crate::wafer::{Wafer, WaferMap};
+
+pub const MAXIMUM_TIERS: u32 = 16;
+
+#[derive(Clone, Copy, Debug, PartialEq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct Stack {
+	pub tiers: u32,
+	pub test_coverage: f32,
+	pub bond_yield: f32,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct StackYield {
+	pub stacks: f32,
+	pub stack_yield: f32,
+}
+
+impl StackYield {
+	pub fn good(&self) -> f32 {
+		self.stacks * self.stack_yield
+	}
+}
+
+impl Stack {
+	fn bond_yield(&self) -> f32 {
+		(self.bond_yield / 100.0).powi(self.tiers as i32 - 1)
+	}
+
+	pub fn die_to_wafer(&self, sites: usize, die_yield: f32) -> StackYield {
+		let coverage = self.test_coverage / 100.0;
+		let pass_rate = die_yield + (1.0 - die_yield) * (1.0 - coverage);
+		let tested_yield = if pass_rate > 0.0 { die_yield / pass_rate } else { 0.0 };
+
+		StackYield {
+			stacks: sites as f32 * pass_rate,
+			stack_yield: tested_yield.powi(self.tiers as i32) * self.bond_yield(),
+		}
+	}
+
+	pub fn wafer_to_wafer(&self, sites: usize, die_yield: f32) -> StackYield {
+		StackYield {
+			stacks: sites as f32,
+			stack_yield: die_yield.powi(self.tiers as i32) * self.bond_yield(),
+		}
+	}
+
+	pub fn simulate_wafer_to_wafer(&self, wafer: &Wafer) -> usize {
+		let maps: Vec<WaferMap> = (0..self.tiers).map(|_| WaferMap::simulate(wafer)).collect();
+		let base = &maps[0];
+
+		let good = base
+			.sites
+			.iter()
+			.enumerate()
+			.flat_map(|(x, column)| (0..column.len()).map(move |y| (x, y)))
+			.filter(|&(x, y)| base.sites[x][y].die_type == DieType::Complete)
+			.filter(|&(x, y)| maps.iter().all(|map| map.sites[x][y].is_good()))
+			.count();
+
+		let bond_failures = ((good as f32) * (1.0 - self.bond_yield())).round() as usize;
+		good - bond_failures.min(good)
+	}
+}
+
+impl Default for Stack {
+	fn default() -> Stack {
+		Stack {
+			tiers: 4,
+			test_coverage: 95.0,
+			bond_yield: 99.0,
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn close(a: f32, b: f32) -> bool {
+		(a - b).abs() < 1e-4
+	}
+
+	#[test]
+	fn stacks_wafer_to_wafer() {
+		let stack = Stack {
+			tiers: 2,
+			test_coverage: 95.0,
+			bond_yield: 99.0,
+		};
+		let stacked = stack.wafer_to_wafer(100, 0.9);
+		assert_eq!(stacked.stacks, 100.0);
+		assert!(close(stacked.stack_yield, 0.81 * 0.99));
+		assert!(close(stacked.good(), 80.19));
+	}
+
+	#[test]
+	fn stacks_tested_dies() {
+		let stack = Stack {
+			tiers: 3,
+			test_coverage: 100.0,
+			bond_yield: 99.0,
+		};
+		let stacked = stack.die_to_wafer(100, 0.9);
+		assert!(close(stacked.stacks, 90.0));
+		assert!(close(stacked.stack_yield, 0.99 * 0.99));
+
+		let untested = Stack {
+			test_coverage: 0.0,
+			..stack
+		};
+		assert_eq!(untested.die_to_wafer(100, 0.9), untested.wafer_to_wafer(100, 0.9));
+
+		let partial = Stack {
+			test_coverage: 50.0,
+			..stack
+		};
+		assert!(partial.die_to_wafer(100, 0.9).good() > partial.wafer_to_wafer(100, 0.9).good());
+		assert_eq!(stack.die_to_wafer(100, 0.0).good(), 0.0);
+	}
+
+	#[test]
+	fn simulates_wafer_to_wafer() {
+		let wafer = Wafer {
+			defect_rate: 0.0,
+			..Wafer::default()
+		};
+		let complete = WaferMap::simulate(&wafer).count(DieType::Complete);
+		let stack = Stack {
+			tiers: 3,
+			test_coverage: 0.0,
+			bond_yield: 100.0,
+		};
+		assert_eq!(stack.simulate_wafer_to_wafer(&wafer), complete);
+
+		let lossy = Stack {
+			bond_yield: 90.0,
+			..stack
+		};
+		let expected = complete - (complete as f32 * (1.0 - 0.81)).round() as usize;
+		assert_eq!(lossy.simulate_wafer_to_wafer(&wafer), expected);
+	}
+}
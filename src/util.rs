@@ -4,7 +4,8 @@
  * file, You can obtain one at http://mozilla.org/MPL/2.0/.
  */
 
-use rand::Rng;
+use rand::seq::index;
+use rand::thread_rng;
 
 #[derive(Clone, Copy, Debug)]
 pub struct Coordinate {
@@ -70,8 +71,8 @@ impl Rectangle {
 	}
 }
 
-pub fn random(min: usize, max: usize) -> usize {
-	rand::thread_rng().gen_range(min..=max)
+pub fn sample(length: usize, amount: usize) -> Vec<usize> {
+	index::sample(&mut thread_rng(), length, amount.min(length)).into_vec()
 }
 
 pub fn min_if(cond: bool, a: f32, b: f32) -> f32 {
@@ -4,44 +4,222 @@
  * file, You can obtain one at http://mozilla.org/MPL/2.0/.
  */
 
+use std::fmt;
+use std::fmt::{Display, Formatter};
+use std::mem;
+use std::path::PathBuf;
+
 use iced::{Alignment, Application, Color, Command, Element, font, Length, Theme};
 use iced::executor::Default as Executor;
 use iced::font::Error;
 use iced::theme::Palette;
-use iced::widget::{column, container, row};
+use iced::widget::{button, column, container, row, scrollable, text, Row};
 use iced_aw::graphics::icons::ICON_FONT_BYTES;
-use iced_aw::grid;
+use iced_aw::{grid, NumberInput};
+use rfd::AsyncFileDialog;
 
+use crate::analysis::{
+	area_waterfall, fit_all, AreaStep, Fit, Sample, Sweep, SweepParameters, SweepResult, Zones, ZonesOption,
+	MAXIMUM_SWEEP_STEPS, MINIMUM_RING_WIDTH, MINIMUM_SWEEP_STEPS,
+};
+use crate::die::MAXIMUM_DIE_BORDER;
+use crate::file::{
+	export_image, export_sites, export_sweep, DefectList, DEFAULT_IMAGE_SIZE, DEFECT_EXTENSIONS, E142_EXTENSION,
+	ExportError, ImageFormat, ImportError, IndexOrigin, MAXIMUM_IMAGE_SIZE, Notch, PROBE_EXTENSIONS, ProbeMap,
+	ProberConvention, Project, PROJECT_EXTENSION, ProjectError, SiteFormat, SubstrateMap, SWEEP_EXTENSION,
+};
+use crate::interposer::{Chiplet, Interposer, MAXIMUM_CHIPLET_COUNT, MAXIMUM_RETICLE_MULTIPLE};
+use crate::partition::{MAXIMUM_CHIPLETS, Partition};
+use crate::scenario::Scenario;
+use crate::stacking::{MAXIMUM_TIERS, Stack};
 use crate::util::min_if;
 use crate::view::components::{
-	critical_area, defect_rate, die_centering, die_size, edge_loss, scribe_lines, shape, translation, yield_model,
+	cost, critical_area, defect_rate, die_border, die_centering, die_size, edge_loss, reticle, reticle_defects,
+	labelled_number, scribe_lines, secondary_die, shape, site_list, stagger, stitching, translation, yield_model,
 };
+use crate::view::compare::{compare, scenarios};
+use crate::view::fit::model_fit;
+use crate::view::interposer::interposer;
+use crate::view::models::yield_models;
+use crate::view::partition::partition;
+use crate::view::shuttle::shuttle;
+use crate::view::spatial::spatial;
+use crate::view::stacking::stacking;
+use crate::view::sweep::sweep;
+use crate::view::zones::zones;
 use crate::view::wafer::WaferViewState;
-use crate::wafer::{Diameter, MAXIMUM_SCRIBE_WIDTH, Panel, Shape, ShapeOption, Wafer, YieldModel};
+use crate::view::waterfall::waterfall;
+use crate::wafer::{
+	Design, Diameter, MAXIMUM_CLUSTER_PARAMETER, MAXIMUM_DEFECT_RATE, MAXIMUM_DESIGN_COPIES, MAXIMUM_EDGE_LOSS,
+	MAXIMUM_SCRIBE_WIDTH, MAXIMUM_STAGGER, MINIMUM_CLUSTER_PARAMETER, MINIMUM_DIE_DIMENSION, Panel, Reticle,
+	ReticleOption, Shape, ShapeOption, Shuttle, Stagger, StaggerOption, Wafer, WaferMap, YieldModel,
+};
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum Component {
 	DieWidth,
 	DieHeight,
+	SealRing,
+	DieKeepOut,
 	Reticle,
+	ReticleWidth,
+	ReticleHeight,
 	CriticalArea,
 	Shape,
 	DefectRate,
+	ClusterParameter,
 	EdgeLoss,
 	ScribeHorizontal,
 	ScribeVertical,
+	FieldScribeHorizontal,
+	FieldScribeVertical,
 	TranslateHorizontal,
 	TranslateVertical,
+	Stagger,
+	RowOffset(usize),
+	Stitching,
+	StitchYieldLoss,
+	StitchKeepOut,
+	Secondary,
+	SecondaryWidth,
+	SecondaryHeight,
+	WaferCost,
+	TestCost,
+	PackagingCost,
+	Chiplets,
+	ChipletOverhead,
+	AssemblyYield,
+	Tiers,
+	TestCoverage,
+	BondYield,
+	InterposerWidth,
+	InterposerHeight,
+	InterposerYield,
+	InterposerAssemblyYield,
+	ChipletCount(usize),
+	ChipletYield(usize),
+	ChipletCost(usize),
+	DesignWidth(usize),
+	DesignHeight(usize),
+	DesignCopies(usize),
+	ExportSize,
+	KillRatio,
+	RingWidth,
+	SweepXStart,
+	SweepXEnd,
+	SweepXSteps,
+	SweepYStart,
+	SweepYEnd,
+	SweepYSteps,
+	XRight,
+	YUp,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Page {
+	#[default]
+	Wafer,
+	Partition,
+	Stacking,
+	Interposer,
+	Shuttle,
+	Compare,
+	Fit,
+	Spatial,
+	Zones,
+	Waterfall,
+	Models,
+	Sweep,
 }
 
-#[derive(Clone, Copy, Debug, PartialEq)]
+impl Page {
+	pub const ALL: &'static [Page] = &[
+		Page::Wafer,
+		Page::Partition,
+		Page::Stacking,
+		Page::Interposer,
+		Page::Shuttle,
+		Page::Compare,
+		Page::Fit,
+		Page::Spatial,
+		Page::Zones,
+		Page::Waterfall,
+		Page::Models,
+		Page::Sweep,
+	];
+}
+
+impl Display for Page {
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		match self {
+			Page::Wafer => f.write_str("Wafer"),
+			Page::Partition => f.write_str("Chiplet Partitioning"),
+			Page::Stacking => f.write_str("3D Stacking"),
+			Page::Interposer => f.write_str("Interposer"),
+			Page::Shuttle => f.write_str("MPW Shuttle"),
+			Page::Compare => f.write_str("Comparison"),
+			Page::Fit => f.write_str("Model Fit"),
+			Page::Spatial => f.write_str("Spatial Statistics"),
+			Page::Zones => f.write_str("Radial Zones"),
+			Page::Waterfall => f.write_str("Area Waterfall"),
+			Page::Models => f.write_str("Yield Models"),
+			Page::Sweep => f.write_str("Parameter Sweep"),
+		}
+	}
+}
+
+#[derive(Clone, Debug, PartialEq)]
 pub enum Message {
+	Page(Page),
 	Center(bool),
 	ShapeOption(ShapeOption),
 	Shape(Shape),
+	ReticleOption(ReticleOption),
+	StaggerOption(StaggerOption),
+	ZonesOption(ZonesOption),
+	SweepParameters(SweepParameters),
+	ReticleDefect((u32, u32), bool),
+	AddChiplet,
+	RemoveChiplet(usize),
+	AddRowOffset,
+	RemoveRowOffset(usize),
+	AddDesign,
+	RemoveDesign(usize),
+	DesignName(usize, String),
+	Scenario(usize),
+	AddScenario,
+	RemoveScenario,
+	ScenarioName(String),
+	CompareLeft(usize),
+	CompareRight(usize),
 	Checkbox(Component, bool),
 	NumberInput(Component, f32),
 	YieldModel(YieldModel),
+	Save,
+	Open,
+	Saved(Result<PathBuf, ProjectError>),
+	Export(ImageFormat),
+	ExportSites(SiteFormat),
+	ExportMap,
+	OpenMap,
+	CloseMap,
+	OpenProbe,
+	CloseProbe,
+	OpenDefects,
+	CloseDefects,
+	AddSample,
+	RemoveSample(usize),
+	ApplyFit(YieldModel),
+	RunSweep,
+	SweepFinished(Sweep, Box<SweepResult>),
+	ExportSweep,
+	IndexOrigin(IndexOrigin),
+	Notch(Notch),
+	Exported(Result<PathBuf, ExportError>),
+	Opened(Result<Box<Project>, ProjectError>),
+	MapOpened(Result<Box<SubstrateMap>, ImportError>),
+	ProbeOpened(Result<Box<ProbeMap>, ImportError>),
+	DefectsOpened(Result<Box<DefectList>, ImportError>),
 	FontLoaded(Result<(), Error>),
 	None,
 }
@@ -58,24 +236,83 @@ impl Message {
 
 pub struct Calculator {
 	wafer: Wafer,
+	wafer_map: WaferMap,
+	opened_map: Option<(String, Wafer, WaferMap)>,
+	probe_map: Option<ProbeMap>,
+	defect_list: Option<DefectList>,
+	kill_ratio: f32,
+	zones: Zones,
+	samples: Vec<Sample>,
+	fits: Vec<Fit>,
+	fitted_cluster: f32,
+	sweep: Sweep,
+	sweep_result: Option<SweepResult>,
+	sweep_running: bool,
 
 	reticle_limit: bool,
 	simple_critical_area: bool,
 	scribe_equal: bool,
 
+	partition: Partition,
+	stack: Stack,
+	stacked_good: usize,
+	area_steps: Vec<AreaStep>,
+	interposer: Interposer,
+	shuttle: Shuttle,
+	convention: ProberConvention,
+
+	scenarios: Vec<Scenario>,
+	active: usize,
+	compared: (usize, usize),
+	compared_maps: (WaferMap, WaferMap),
+
+	page: Page,
+	status: Option<String>,
+	export_size: u32,
 	wafer_view: WaferViewState,
+	compared_views: (WaferViewState, WaferViewState),
 }
 
 impl Default for Calculator {
 	fn default() -> Calculator {
+		let wafer = Wafer::default();
 		Calculator {
-			wafer: Wafer::default(),
+			wafer_map: WaferMap::simulate(&wafer),
+			wafer,
+			opened_map: None,
+			probe_map: None,
+			defect_list: None,
+			kill_ratio: 0.5,
+			zones: Zones::default(),
+			samples: Vec::new(),
+			fits: Vec::new(),
+			fitted_cluster: wafer.cluster,
+			sweep: Sweep::new(SweepParameters::default(), &wafer),
+			sweep_result: None,
+			sweep_running: false,
 
 			reticle_limit: true,
 			simple_critical_area: true,
 			scribe_equal: false,
 
+			partition: Partition::default(),
+			stack: Stack::default(),
+			stacked_good: 0,
+			area_steps: Vec::new(),
+			interposer: Interposer::default(),
+			shuttle: Shuttle::default(),
+			convention: ProberConvention::default(),
+
+			scenarios: vec![Scenario::default()],
+			active: 0,
+			compared: (0, 0),
+			compared_maps: (WaferMap::default(), WaferMap::default()),
+
+			page: Page::default(),
+			status: None,
+			export_size: DEFAULT_IMAGE_SIZE,
 			wafer_view: WaferViewState::default(),
+			compared_views: (WaferViewState::default(), WaferViewState::default()),
 		}
 	}
 }
@@ -99,21 +336,35 @@ impl Application for Calculator {
 
 	fn update(&mut self, message: Message) -> Command<Message> {
 		match message {
+			Message::Page(page) => self.page = page,
 			Message::Center(b) => self.wafer.centered = b,
 			Message::Checkbox(c, b) => match c {
-				Component::DieWidth if b => self.wafer.die = self.wafer.die.square(self.reticle_limit),
+				Component::DieWidth if b => {
+					self.wafer.die = self.wafer.die.square(self.reticle_limit, self.wafer.reticle)
+				}
 				Component::DieWidth => self.wafer.die = self.wafer.die.rectangle(),
-				Component::Reticle if b => {
-					self.reticle_limit = true;
-					self.wafer.die = self.wafer.die.clamp_reticle();
+				Component::Reticle => {
+					self.reticle_limit = b;
+					if b {
+						self.wafer.stitching.enabled = false;
+					}
+				}
+				Component::Stitching => {
+					self.wafer.stitching.enabled = b;
+					if b {
+						self.reticle_limit = false;
+					}
 				}
-				Component::Reticle => self.reticle_limit = false,
 				Component::CriticalArea => {
 					self.simple_critical_area = b;
 				}
+				Component::Secondary => self.wafer.secondary.enabled = b,
+				Component::XRight => self.convention.x_right = b,
+				Component::YUp => self.convention.y_up = b,
 				Component::ScribeHorizontal => {
 					self.scribe_equal = b;
 					self.wafer.scribe_lanes.1 = self.wafer.scribe_lanes.0;
+					self.wafer.field_scribe_lanes.1 = self.wafer.field_scribe_lanes.0;
 				}
 				_ => {}
 			},
@@ -126,14 +377,83 @@ impl Application for Calculator {
 					ShapeOption::Panel => Shape::Panel(Panel::default()),
 				};
 			}
+			Message::ZonesOption(opt) => {
+				self.zones = match opt {
+					ZonesOption::CenterMiddleEdge => Zones::CenterMiddleEdge,
+					ZonesOption::Rings => Zones::Rings(25.0),
+				};
+			}
+			Message::ReticleOption(opt) => {
+				let (width, height) = self.wafer.reticle.dimensions();
+				self.wafer.reticle = match opt {
+					ReticleOption::FullField => Reticle::FullField,
+					ReticleOption::HighNa => Reticle::HighNa,
+					ReticleOption::ILine => Reticle::ILine,
+					ReticleOption::Custom => Reticle::Custom { width, height },
+				};
+			}
+			Message::StaggerOption(opt) => {
+				let fraction = if self.wafer.stagger.is_staggered() {
+					self.wafer.stagger.fraction()
+				} else {
+					0.5
+				};
+				self.wafer.stagger = match opt {
+					StaggerOption::None => Stagger::None,
+					StaggerOption::Alternating => Stagger::Alternating(fraction),
+					StaggerOption::Progressive => Stagger::Progressive(fraction),
+					StaggerOption::PerRow => Stagger::PerRow(vec![0.0, fraction]),
+				};
+			}
+			Message::ReticleDefect(position, b) => {
+				if b {
+					self.wafer.reticle_defects.insert(position);
+				} else {
+					self.wafer.reticle_defects.remove(&position);
+				}
+			}
+			Message::AddChiplet => self.interposer.chiplets.push(Chiplet::default()),
+			Message::RemoveChiplet(i) => {
+				if i < self.interposer.chiplets.len() {
+					self.interposer.chiplets.remove(i);
+				}
+			}
+			Message::AddRowOffset => {
+				if let Stagger::PerRow(offsets) = &mut self.wafer.stagger {
+					offsets.push(0.0);
+				}
+			}
+			Message::RemoveRowOffset(i) => {
+				if let Stagger::PerRow(offsets) = &mut self.wafer.stagger {
+					if i < offsets.len() {
+						offsets.remove(i);
+					}
+				}
+			}
+			Message::AddDesign => self.shuttle.designs.push(Design::default()),
+			Message::RemoveDesign(i) => {
+				if i < self.shuttle.designs.len() {
+					self.shuttle.designs.remove(i);
+				}
+			}
+			Message::DesignName(i, name) => {
+				if let Some(design) = self.shuttle.designs.get_mut(i) {
+					design.name = name;
+				}
+			}
 			Message::NumberInput(c, mut f) => match c {
 				Component::DieWidth => self.wafer.die = self.wafer.die.new_width(f),
 				Component::DieHeight => self.wafer.die = self.wafer.die.new_height(f),
+				Component::SealRing => self.wafer.die.seal_ring = f.clamp(0.0, MAXIMUM_DIE_BORDER),
+				Component::DieKeepOut => self.wafer.die.keep_out = f.clamp(0.0, MAXIMUM_DIE_BORDER),
 				Component::CriticalArea => {
-					self.wafer.critical_area = min_if(!self.simple_critical_area, f, self.wafer.die.area());
+					self.wafer.critical_area = min_if(!self.simple_critical_area, f, self.wafer.usable_area());
+				}
+				Component::DefectRate => self.wafer.defect_rate = f.clamp(0.0, MAXIMUM_DEFECT_RATE),
+				Component::ClusterParameter => {
+					self.wafer.cluster = f.clamp(MINIMUM_CLUSTER_PARAMETER, MAXIMUM_CLUSTER_PARAMETER)
 				}
-				Component::DefectRate => self.wafer.defect_rate = f,
-				Component::EdgeLoss => self.wafer.edge_loss = f,
+				Component::EdgeLoss => self.wafer.edge_loss = f.clamp(0.0, MAXIMUM_EDGE_LOSS),
 				Component::ScribeHorizontal => {
 					f = f.min(MAXIMUM_SCRIBE_WIDTH);
 					self.wafer.scribe_lanes.0 = f;
@@ -145,19 +465,329 @@ impl Application for Calculator {
 					f = f.min(MAXIMUM_SCRIBE_WIDTH);
 					self.wafer.scribe_lanes.1 = f;
 				}
+				Component::FieldScribeHorizontal => {
+					f = f.min(MAXIMUM_SCRIBE_WIDTH);
+					self.wafer.field_scribe_lanes.0 = f;
+					if self.scribe_equal {
+						self.wafer.field_scribe_lanes.1 = f;
+					}
+				}
+				Component::FieldScribeVertical => {
+					f = f.min(MAXIMUM_SCRIBE_WIDTH);
+					self.wafer.field_scribe_lanes.1 = f;
+				}
 				Component::TranslateHorizontal => self.wafer.translation.0 = f,
 				Component::TranslateVertical => self.wafer.translation.1 = f,
+				Component::Stagger => {
+					f = f.clamp(0.0, MAXIMUM_STAGGER);
+					match &mut self.wafer.stagger {
+						Stagger::Alternating(fraction) | Stagger::Progressive(fraction) => *fraction = f,
+						_ => {}
+					}
+				}
+				Component::RowOffset(i) => {
+					if let Stagger::PerRow(offsets) = &mut self.wafer.stagger {
+						if let Some(offset) = offsets.get_mut(i) {
+							*offset = f.clamp(0.0, MAXIMUM_STAGGER);
+						}
+					}
+				}
+				Component::Chiplets => self.partition.max_chiplets = (f.round() as u32).clamp(1, MAXIMUM_CHIPLETS),
+				Component::ChipletOverhead => self.partition.overhead = f.clamp(0.0, 100.0),
+				Component::AssemblyYield => self.partition.assembly_yield = f.clamp(0.0, 100.0),
+				Component::Tiers => self.stack.tiers = (f.round() as u32).clamp(1, MAXIMUM_TIERS),
+				Component::TestCoverage => self.stack.test_coverage = f.clamp(0.0, 100.0),
+				Component::BondYield => self.stack.bond_yield = f.clamp(0.0, 100.0),
+				Component::InterposerWidth => self.interposer.reticles.0 = f.clamp(0.1, MAXIMUM_RETICLE_MULTIPLE),
+				Component::InterposerHeight => self.interposer.reticles.1 = f.clamp(0.1, MAXIMUM_RETICLE_MULTIPLE),
+				Component::InterposerYield => self.interposer.interposer_yield = f.clamp(0.0, 100.0),
+				Component::InterposerAssemblyYield => self.interposer.assembly_yield = f.clamp(0.0, 100.0),
+				Component::ChipletCount(i) => {
+					if let Some(chiplet) = self.interposer.chiplets.get_mut(i) {
+						chiplet.count = (f.round() as u32).clamp(1, MAXIMUM_CHIPLET_COUNT);
+					}
+				}
+				Component::ChipletYield(i) => {
+					if let Some(chiplet) = self.interposer.chiplets.get_mut(i) {
+						chiplet.die_yield = f.clamp(0.0, 100.0);
+					}
+				}
+				Component::ChipletCost(i) => {
+					if let Some(chiplet) = self.interposer.chiplets.get_mut(i) {
+						chiplet.cost = f.max(0.0);
+					}
+				}
+				Component::DesignWidth(i) => {
+					if let Some(design) = self.shuttle.designs.get_mut(i) {
+						design.width = f.clamp(MINIMUM_DIE_DIMENSION, self.wafer.reticle.long());
+					}
+				}
+				Component::DesignHeight(i) => {
+					if let Some(design) = self.shuttle.designs.get_mut(i) {
+						design.height = f.clamp(MINIMUM_DIE_DIMENSION, self.wafer.reticle.long());
+					}
+				}
+				Component::DesignCopies(i) => {
+					if let Some(design) = self.shuttle.designs.get_mut(i) {
+						design.copies = (f.round() as u32).min(MAXIMUM_DESIGN_COPIES);
+					}
+				}
+				Component::KillRatio => self.kill_ratio = f.clamp(0.0, 1.0),
+				Component::RingWidth => self.zones = Zones::Rings(f.max(MINIMUM_RING_WIDTH)),
+				Component::SweepXStart => self.sweep.x.start = f,
+				Component::SweepXEnd => self.sweep.x.end = f,
+				Component::SweepXSteps => {
+					self.sweep.x.steps = (f.round() as usize).clamp(MINIMUM_SWEEP_STEPS, MAXIMUM_SWEEP_STEPS)
+				}
+				Component::SweepYStart => self.sweep.y.start = f,
+				Component::SweepYEnd => self.sweep.y.end = f,
+				Component::SweepYSteps => {
+					self.sweep.y.steps = (f.round() as usize).clamp(MINIMUM_SWEEP_STEPS, MAXIMUM_SWEEP_STEPS)
+				}
+				Component::ExportSize => self.export_size = (f.round() as u32).clamp(1, MAXIMUM_IMAGE_SIZE),
+				Component::WaferCost => self.wafer.cost.wafer = f.max(0.0),
+				Component::TestCost => self.wafer.cost.test = f.max(0.0),
+				Component::PackagingCost => self.wafer.cost.packaging = f.max(0.0),
+				Component::StitchYieldLoss => self.wafer.stitching.yield_loss = f.clamp(0.0, 100.0),
+				Component::StitchKeepOut => self.wafer.stitching.keep_out = f.max(0.0),
+				Component::SecondaryWidth => self.wafer.secondary.width = f.max(MINIMUM_DIE_DIMENSION),
+				Component::SecondaryHeight => self.wafer.secondary.height = f.max(MINIMUM_DIE_DIMENSION),
+				Component::ReticleWidth => {
+					if let Reticle::Custom { width, .. } = &mut self.wafer.reticle {
+						*width = f.max(MINIMUM_DIE_DIMENSION);
+					}
+				}
+				Component::ReticleHeight => {
+					if let Reticle::Custom { height, .. } = &mut self.wafer.reticle {
+						*height = f.max(MINIMUM_DIE_DIMENSION);
+					}
+				}
 				_ => {}
 			},
 			Message::YieldModel(m) => self.wafer.yield_model = m,
+			Message::Scenario(i) => {
+				if i < self.scenarios.len() {
+					self.restore_scenario(i);
+				}
+			}
+			Message::AddScenario => {
+				let scenario = Scenario {
+					name: format!("Scenario {}", self.scenarios.len() + 1),
+					..self.scenarios[self.active].clone()
+				};
+				self.scenarios.push(scenario);
+				self.restore_scenario(self.scenarios.len() - 1);
+			}
+			Message::RemoveScenario => {
+				if self.scenarios.len() > 1 {
+					self.scenarios.remove(self.active);
+					let last = self.scenarios.len() - 1;
+					self.compared = (self.compared.0.min(last), self.compared.1.min(last));
+					self.restore_scenario(self.active.min(last));
+				}
+			}
+			Message::ScenarioName(name) => self.scenarios[self.active].name = name,
+			Message::CompareLeft(i) => self.compared.0 = i.min(self.scenarios.len() - 1),
+			Message::CompareRight(i) => self.compared.1 = i.min(self.scenarios.len() - 1),
+			Message::Save => {
+				return Command::perform(save_project(self.project()), |result| {
+					result.map_or(Message::None, Message::Saved)
+				});
+			}
+			Message::Open => {
+				return Command::perform(open_project(), |result| result.map_or(Message::None, Message::Opened));
+			}
+			Message::Saved(result) => {
+				self.status = Some(match result {
+					Ok(path) => format!("Saved {}", path.display()),
+					Err(error) => error.to_string(),
+				});
+			}
+			Message::Export(format) => {
+				let export = export_wafer(self.wafer.clone(), self.wafer_map.clone(), self.export_size, format);
+				return Command::perform(export, |result| result.map_or(Message::None, Message::Exported));
+			}
+			Message::ExportSites(format) => {
+				let export = export_site_list(self.wafer.clone(), self.wafer_map.clone(), self.convention, format);
+				return Command::perform(export, |result| result.map_or(Message::None, Message::Exported));
+			}
+			Message::ExportMap => {
+				let map = SubstrateMap::new(&self.scenarios[self.active].name, &self.wafer, &self.wafer_map);
+				return Command::perform(export_substrate_map(map), |result| {
+					result.map_or(Message::None, Message::Exported)
+				});
+			}
+			Message::OpenMap => {
+				return Command::perform(open_substrate_map(), |result| {
+					result.map_or(Message::None, Message::MapOpened)
+				});
+			}
+			Message::MapOpened(result) => match result {
+				Ok(map) => {
+					let (wafer, wafer_map) = map.wafer_map();
+					self.opened_map = Some((map.substrate_id, wafer, wafer_map));
+					self.page = Page::Wafer;
+					self.status = None;
+				}
+				Err(error) => self.status = Some(error.to_string()),
+			},
+			Message::CloseMap => self.opened_map = None,
+			Message::OpenProbe => {
+				return Command::perform(open_probe_map(), |result| {
+					result.map_or(Message::None, Message::ProbeOpened)
+				});
+			}
+			Message::ProbeOpened(result) => match result {
+				Ok(probe) => {
+					let measured = probe.measure(&self.wafer, &self.wafer_map, self.convention).len();
+					self.status = Some(format!(
+						"Loaded {}: {} of {} dies on the grid",
+						probe.name,
+						measured,
+						probe.dies.len()
+					));
+					self.probe_map = Some(*probe);
+					self.page = Page::Wafer;
+				}
+				Err(error) => self.status = Some(error.to_string()),
+			},
+			Message::CloseProbe => self.probe_map = None,
+			Message::OpenDefects => {
+				return Command::perform(open_defect_list(), |result| {
+					result.map_or(Message::None, Message::DefectsOpened)
+				});
+			}
+			Message::DefectsOpened(result) => match result {
+				Ok(defects) => {
+					let inspection = self.wafer.inspect(&self.wafer_map, &defects.defects, self.kill_ratio);
+					let on_dies = inspection.defects.iter().filter(|defect| defect.site.is_some()).count();
+					self.status = Some(format!(
+						"Loaded {}: {} defects, {} on complete dies",
+						defects.name,
+						defects.defects.len(),
+						on_dies
+					));
+					self.defect_list = Some(*defects);
+					self.wafer_map.inspection = Some(inspection);
+					self.page = Page::Wafer;
+				}
+				Err(error) => self.status = Some(error.to_string()),
+			},
+			Message::CloseDefects => self.defect_list = None,
+			Message::AddSample => {
+				if let Some(probe) = &self.probe_map {
+					if self.wafer_map.measured.is_empty() {
+						self.status = Some(format!("{} has no dies on the current layout", probe.name));
+					} else {
+						self.samples.push(Sample {
+							name: probe.name.clone(),
+							area: self.wafer.critical_area,
+							tested: self.wafer_map.measured.len(),
+							good: self.wafer_map.measured_good(),
+						});
+						self.refit();
+					}
+				}
+			}
+			Message::RemoveSample(i) => {
+				if i < self.samples.len() {
+					self.samples.remove(i);
+					self.refit();
+				}
+			}
+			Message::ApplyFit(model) => {
+				if let Some(fit) = self.fits.iter().find(|fit| fit.model == model) {
+					self.wafer.yield_model = fit.model;
+					self.wafer.defect_rate = fit.defect_rate;
+					self.wafer.cluster = fit.cluster;
+				}
+			}
+			Message::SweepParameters(parameters) => {
+				self.sweep = Sweep::new(parameters, &self.wafer);
+				self.sweep_result = None;
+			}
+			Message::RunSweep => {
+				self.sweep_running = true;
+				let (sweep, scenario) = (self.sweep, self.scenarios[self.active].clone());
+				return Command::perform(async move { (sweep, sweep.run(&scenario)) }, |(sweep, result)| {
+					Message::SweepFinished(sweep, Box::new(result))
+				});
+			}
+			Message::SweepFinished(sweep, result) => {
+				self.sweep_running = false;
+				if sweep == self.sweep {
+					self.sweep_result = Some(*result);
+				}
+			}
+			Message::ExportSweep => {
+				if let Some(result) = self.sweep_result.clone() {
+					return Command::perform(export_sweep_result(result), |result| {
+						result.map_or(Message::None, Message::Exported)
+					});
+				}
+			}
+			Message::IndexOrigin(origin) => self.convention.origin = origin,
+			Message::Notch(notch) => self.convention.notch = notch,
+			Message::Exported(result) => {
+				self.status = Some(match result {
+					Ok(path) => format!("Exported {}", path.display()),
+					Err(error) => error.to_string(),
+				});
+			}
+			Message::Opened(result) => match result {
+				Ok(project) => {
+					self.load_project(*project);
+					self.status = None;
+				}
+				Err(error) => self.status = Some(error.to_string()),
+			},
 			_ => {}
 		}
 
+		if self.reticle_limit {
+			self.wafer.die = self.wafer.die.clamp_reticle(self.wafer.reticle);
+		}
+
 		if self.simple_critical_area {
-			self.wafer.critical_area = self.wafer.die.area();
+			self.wafer.critical_area = self.wafer.usable_area();
 		} else {
 			self.wafer.clamp_critical_area();
 		}
+		self.wafer.clamp_reticle_defects();
+		if self.fitted_cluster != self.wafer.cluster {
+			self.refit();
+		}
+
+		let mut previous = mem::replace(&mut self.wafer_map, WaferMap::simulate(&self.wafer));
+		if let Some(probe) = &self.probe_map {
+			self.wafer_map.measured = probe.measure(&self.wafer, &self.wafer_map, self.convention);
+		}
+		if let Some(defects) = &self.defect_list {
+			let inspection = match previous.inspection.take() {
+				Some(mut inspection) if inspection.is_current(&self.wafer, &previous, &self.wafer_map) => {
+					inspection.kill(self.kill_ratio);
+					inspection
+				}
+				_ => self.wafer.inspect(&self.wafer_map, &defects.defects, self.kill_ratio),
+			};
+			self.wafer_map.inspection = Some(inspection);
+		}
+		if self.page == Page::Stacking {
+			self.stacked_good = self.stack.simulate_wafer_to_wafer(&self.wafer);
+		}
+		if self.page == Page::Waterfall {
+			self.area_steps = area_waterfall(&self.wafer, &self.wafer_map);
+		}
+
+		self.store_scenario();
+		if self.page == Page::Compare {
+			self.compared_maps = (
+				WaferMap::simulate(&self.scenarios[self.compared.0].wafer),
+				WaferMap::simulate(&self.scenarios[self.compared.1].wafer),
+			);
+			self.compared_views.0.request_redraw();
+			self.compared_views.1.request_redraw();
+		}
 
 		self.wafer_view.request_redraw();
 		Command::none()
@@ -165,42 +795,146 @@ impl Application for Calculator {
 
 	fn view(&self) -> Element<'_, Message> {
 		let die_size_inputs = die_size(&self.wafer, self.reticle_limit);
+		let die_border_inputs = die_border(self.wafer.die);
 		let critical_area_inputs = critical_area(&self.wafer, self.simple_critical_area);
 		let shape_input = shape(self.wafer.shape);
+		let reticle_input = reticle(self.wafer.reticle);
 		let defect_rate_input = defect_rate(self.wafer.defect_rate);
 		let edge_loss_input = edge_loss(self.wafer.edge_loss);
 		let scribe_lanes_inputs = scribe_lines(&self.wafer, self.scribe_equal);
+		let stitching_inputs = stitching(&self.wafer);
+		let secondary_inputs = secondary_die(&self.wafer);
 		let translation_inputs = translation(&self.wafer);
 		let centering_input = die_centering(self.wafer.centered);
-		let yield_model_input = yield_model(self.wafer.yield_model);
+		let stagger_input = stagger(&self.wafer);
+		let yield_model_input = yield_model(self.wafer.yield_model, self.wafer.cluster);
+		let reticle_defects_input = reticle_defects(&self.wafer);
+		let cost_inputs = cost(self.wafer.cost);
+		let site_list_inputs = site_list(self.convention);
+		let kill_ratio_input = labelled_number(
+			"Defect Kill Ratio",
+			self.kill_ratio,
+			(0.0, 1.0),
+			0.05,
+			Component::KillRatio,
+		);
 
 		let options = grid![
 			die_size_inputs,
+			die_border_inputs,
 			critical_area_inputs,
 			shape_input,
+			reticle_input,
 			defect_rate_input,
 			edge_loss_input,
 			scribe_lanes_inputs,
+			stitching_inputs,
+			secondary_inputs,
 			translation_inputs,
 			centering_input,
+			stagger_input,
 			yield_model_input,
+			reticle_defects_input,
+			cost_inputs,
+			site_list_inputs,
+			kill_ratio_input,
 		]
 		.column_spacing(16.0)
 		.row_spacing(1.0);
+		let options = scrollable(options).height(Length::Fill);
+
+		let page: Element<'_, Message> = match self.page {
+			Page::Wafer => {
+				let (wafer, wafer_map) = self.displayed_map();
+				let wafer_view = container(self.wafer_view.view(wafer, wafer_map))
+					.height(Length::Fill)
+					.width(Length::Fill)
+					.padding(4)
+					.center_x()
+					.center_y();
 
-		let wafer_view = container(self.wafer_view.view(&self.wafer))
-			.height(Length::Fill)
-			.width(Length::Fill)
-			.padding(4)
-			.center_x()
-			.center_y();
+				match &self.opened_map {
+					Some((substrate_id, _, _)) => {
+						let header = row![
+							text(format!("Substrate Map: {}", substrate_id)),
+							button(text("Close Map")).on_press(Message::CloseMap),
+						]
+						.spacing(8)
+						.align_items(Alignment::Center);
+						column![header, wafer_view].align_items(Alignment::Center).into()
+					}
+					None => column![wafer_view].align_items(Alignment::Center).into(),
+				}
+			}
+			Page::Partition => partition(&self.wafer, self.partition),
+			Page::Stacking => stacking(&self.wafer, &self.wafer_map, self.stack, self.stacked_good),
+			Page::Interposer => interposer(&self.wafer, &self.interposer),
+			Page::Shuttle => shuttle(&self.wafer, &self.shuttle),
+			Page::Spatial => {
+				let (wafer, wafer_map) = self.displayed_map();
+				spatial(wafer, wafer_map)
+			}
+			Page::Zones => {
+				let (wafer, wafer_map) = self.displayed_map();
+				zones(wafer, wafer_map, self.zones)
+			}
+			Page::Waterfall => waterfall(&self.area_steps),
+			Page::Models => yield_models(&self.wafer),
+			Page::Sweep => sweep(&self.wafer, self.sweep, self.sweep_result.as_ref(), self.sweep_running),
+			Page::Fit => model_fit(&self.samples, &self.fits, self.probe_map.is_some()),
+			Page::Compare => compare(
+				&self.scenarios,
+				self.compared,
+				&self.compared_maps,
+				&self.compared_views,
+			),
+		};
 
-		let wafer_view_column = column![wafer_view].align_items(Alignment::Center);
+		let pages = Row::with_children(
+			Page::ALL
+				.iter()
+				.map(|&page| button(text(page.to_string())).on_press(Message::Page(page)).into())
+				.collect(),
+		)
+		.spacing(4);
+		let mut files = row![
+			button(text("Open")).on_press(Message::Open),
+			button(text("Save")).on_press(Message::Save),
+			button(text("Export SVG")).on_press(Message::Export(ImageFormat::Svg)),
+			button(text("Export PNG")).on_press(Message::Export(ImageFormat::Png)),
+			button(text("Export E142")).on_press(Message::ExportMap),
+			button(text("Open Map")).on_press(Message::OpenMap),
+			if self.probe_map.is_some() {
+				button(text("Close Probe Map")).on_press(Message::CloseProbe)
+			} else {
+				button(text("Open Probe Map")).on_press(Message::OpenProbe)
+			},
+			if self.defect_list.is_some() {
+				button(text("Close Defects")).on_press(Message::CloseDefects)
+			} else {
+				button(text("Open Defects")).on_press(Message::OpenDefects)
+			},
+			NumberInput::new(
+				self.export_size as f32,
+				MAXIMUM_IMAGE_SIZE as f32,
+				Message::number_input(Component::ExportSize),
+			)
+			.min(64.0)
+			.step(64.0),
+			text("px"),
+		]
+		.spacing(8)
+		.align_items(Alignment::Center);
+		if let Some(status) = &self.status {
+			files = files.push(text(status));
+		}
 
-		let content = row![options, wafer_view_column]
+		let content = row![options, column![files, pages, page].spacing(8)]
 			.spacing(4)
-			.padding(16)
 			.align_items(Alignment::Center);
+		let content = column![scenarios(&self.scenarios, self.active), content]
+			.spacing(8)
+			.padding(16);
 
 		container(content).center_x().center_y().into()
 	}
@@ -211,3 +945,144 @@ impl Application for Calculator {
 		Theme::custom(palette)
 	}
 }
+
+impl Calculator {
+	fn store_scenario(&mut self) {
+		let scenario = &mut self.scenarios[self.active];
+		scenario.wafer = self.wafer.clone();
+		scenario.reticle_limit = self.reticle_limit;
+		scenario.simple_critical_area = self.simple_critical_area;
+		scenario.scribe_equal = self.scribe_equal;
+	}
+
+	fn restore_scenario(&mut self, i: usize) {
+		let scenario = self.scenarios[i].clone();
+		self.active = i;
+		self.wafer = scenario.wafer;
+		self.reticle_limit = scenario.reticle_limit;
+		self.simple_critical_area = scenario.simple_critical_area;
+		self.scribe_equal = scenario.scribe_equal;
+	}
+
+	fn refit(&mut self) {
+		self.fits = fit_all(&self.samples, self.wafer.cluster);
+		self.fitted_cluster = self.wafer.cluster;
+	}
+
+	fn displayed_map(&self) -> (&Wafer, &WaferMap) {
+		match &self.opened_map {
+			Some((_, wafer, wafer_map)) => (wafer, wafer_map),
+			None => (&self.wafer, &self.wafer_map),
+		}
+	}
+
+	fn project(&self) -> Project {
+		Project {
+			scenarios: self.scenarios.clone(),
+			active: self.active,
+			partition: self.partition,
+			stack: self.stack,
+			interposer: self.interposer.clone(),
+			shuttle: self.shuttle.clone(),
+			convention: self.convention,
+			..Project::default()
+		}
+	}
+
+	fn load_project(&mut self, project: Project) {
+		self.scenarios = project.scenarios;
+		self.compared = (0, 0);
+		self.restore_scenario(project.active);
+		self.partition = project.partition;
+		self.stack = project.stack;
+		self.interposer = project.interposer;
+		self.shuttle = project.shuttle;
+		self.convention = project.convention;
+	}
+}
+
+async fn save_project(project: Project) -> Option<Result<PathBuf, ProjectError>> {
+	let handle = AsyncFileDialog::new()
+		.add_filter("Project", &[PROJECT_EXTENSION])
+		.set_file_name("project.json")
+		.save_file()
+		.await?;
+	let path = handle.path().to_path_buf();
+	Some(project.save(&path).map(|_| path))
+}
+
+async fn export_wafer(
+	wafer: Wafer, map: WaferMap, size: u32, format: ImageFormat,
+) -> Option<Result<PathBuf, ExportError>> {
+	let handle = AsyncFileDialog::new()
+		.add_filter("Image", &[format.extension()])
+		.set_file_name(format!("wafer.{}", format.extension()))
+		.save_file()
+		.await?;
+	let path = handle.path().to_path_buf();
+	Some(export_image(&path, format, &wafer, &map, size).map(|_| path))
+}
+
+async fn export_site_list(
+	wafer: Wafer, map: WaferMap, convention: ProberConvention, format: SiteFormat,
+) -> Option<Result<PathBuf, ExportError>> {
+	let handle = AsyncFileDialog::new()
+		.add_filter("Site List", &[format.extension()])
+		.set_file_name(format!("sites.{}", format.extension()))
+		.save_file()
+		.await?;
+	let path = handle.path().to_path_buf();
+	Some(export_sites(&path, format, &wafer, &map, convention).map(|_| path))
+}
+
+async fn export_sweep_result(result: SweepResult) -> Option<Result<PathBuf, ExportError>> {
+	let handle = AsyncFileDialog::new()
+		.add_filter("Sweep", &[SWEEP_EXTENSION])
+		.set_file_name(format!("sweep.{}", SWEEP_EXTENSION))
+		.save_file()
+		.await?;
+	let path = handle.path().to_path_buf();
+	Some(export_sweep(&path, &result).map(|_| path))
+}
+
+async fn export_substrate_map(map: SubstrateMap) -> Option<Result<PathBuf, ExportError>> {
+	let handle = AsyncFileDialog::new()
+		.add_filter("SEMI E142 Map", &[E142_EXTENSION])
+		.set_file_name(format!("map.{}", E142_EXTENSION))
+		.save_file()
+		.await?;
+	let path = handle.path().to_path_buf();
+	Some(map.save(&path).map(|_| path))
+}
+
+async fn open_substrate_map() -> Option<Result<Box<SubstrateMap>, ImportError>> {
+	let handle = AsyncFileDialog::new()
+		.add_filter("SEMI E142 Map", &[E142_EXTENSION])
+		.pick_file()
+		.await?;
+	Some(SubstrateMap::load(handle.path()).map(Box::new))
+}
+
+async fn open_probe_map() -> Option<Result<Box<ProbeMap>, ImportError>> {
+	let handle = AsyncFileDialog::new()
+		.add_filter("Probe Map", PROBE_EXTENSIONS)
+		.pick_file()
+		.await?;
+	Some(ProbeMap::load(handle.path()).map(Box::new))
+}
+
+async fn open_defect_list() -> Option<Result<Box<DefectList>, ImportError>> {
+	let handle = AsyncFileDialog::new()
+		.add_filter("Defect List", DEFECT_EXTENSIONS)
+		.pick_file()
+		.await?;
+	Some(DefectList::load(handle.path()).map(Box::new))
+}
+
+async fn open_project() -> Option<Result<Box<Project>, ProjectError>> {
+	let handle = AsyncFileDialog::new()
+		.add_filter("Project", &[PROJECT_EXTENSION])
+		.pick_file()
+		.await?;
+	Some(Project::load(handle.path()).map(Box::new))
+}
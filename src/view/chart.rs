@@ -0,0 +1,425 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+use iced::{alignment, Color, Length, Point, Rectangle, Renderer, Size, Theme, Vector};
+use iced::mouse::Cursor;
+use iced::widget::Canvas;
+use iced::widget::canvas::{Frame, Geometry, Path, Program, Stroke, Text};
+
+use crate::view::calculator::Message;
+
+const MARGIN_LEFT: f32 = 64.0;
+const MARGIN_RIGHT: f32 = 16.0;
+const MARGIN_TOP: f32 = 32.0;
+const MARGIN_BOTTOM: f32 = 40.0;
+
+pub const PALETTE: &[Color] = &[
+	Color::from_rgb(0.24, 0.47, 0.85),
+	Color::from_rgb(0.85, 0.37, 0.01),
+	Color::from_rgb(0.11, 0.62, 0.47),
+	Color::from_rgb(0.46, 0.44, 0.70),
+	Color::from_rgb(0.91, 0.16, 0.54),
+	Color::from_rgb(0.40, 0.65, 0.12),
+];
+
+#[derive(Clone, Debug)]
+pub struct Series {
+	pub label: String,
+	pub color: Color,
+	pub points: Vec<(f32, f32)>,
+}
+
+#[derive(Clone, Debug)]
+pub struct LineChart {
+	pub title: String,
+	pub x_label: String,
+	pub y_label: String,
+	pub series: Vec<Series>,
+	pub markers: Vec<(f32, f32)>,
+}
+
+#[derive(Clone, Debug)]
+pub struct BarChart {
+	pub title: String,
+	pub y_label: String,
+	pub categories: Vec<String>,
+	pub series: Vec<Series>,
+}
+
+#[derive(Clone, Debug)]
+pub struct WaterfallChart {
+	pub title: String,
+	pub y_label: String,
+	pub steps: Vec<(String, f32, bool)>,
+}
+
+#[derive(Clone, Debug)]
+pub struct Heatmap {
+	pub title: String,
+	pub x_label: String,
+	pub y_label: String,
+	pub x: Vec<f32>,
+	pub y: Vec<f32>,
+	pub values: Vec<Vec<f32>>,
+	pub marker: Option<(f32, f32)>,
+}
+
+impl LineChart {
+	pub fn view(self) -> Canvas<LineChart, Message> {
+		Canvas::new(self).width(Length::Fill).height(Length::Fill)
+	}
+}
+
+impl BarChart {
+	pub fn view(self) -> Canvas<BarChart, Message> {
+		Canvas::new(self).width(Length::Fill).height(Length::Fill)
+	}
+}
+
+pub struct Plot {
+	pub origin: Point,
+	pub size: Size,
+	pub x_range: (f32, f32),
+	pub y_range: (f32, f32),
+}
+
+impl Plot {
+	pub fn new(bounds: Size, x_range: (f32, f32), y_range: (f32, f32)) -> Plot {
+		Plot {
+			origin: Point::new(MARGIN_LEFT, bounds.height - MARGIN_BOTTOM),
+			size: Size::new(
+				(bounds.width - MARGIN_LEFT - MARGIN_RIGHT).max(1.0),
+				(bounds.height - MARGIN_TOP - MARGIN_BOTTOM).max(1.0),
+			),
+			x_range: widen(x_range),
+			y_range: widen(y_range),
+		}
+	}
+
+	pub fn point(&self, x: f32, y: f32) -> Point {
+		let x = (x - self.x_range.0) / (self.x_range.1 - self.x_range.0);
+		let y = (y - self.y_range.0) / (self.y_range.1 - self.y_range.0);
+		self.origin + Vector::new(x * self.size.width, -y * self.size.height)
+	}
+
+	pub fn draw_axes(&self, frame: &mut Frame, title: &str, x_label: &str, y_label: &str) {
+		self.draw_frame(frame, title, x_label, y_label);
+
+		let bottom_right = self.origin + Vector::new(self.size.width, 0.0);
+		for (value, position) in [(self.x_range.0, self.origin), (self.x_range.1, bottom_right)] {
+			frame.fill_text(Text {
+				content: format_tick(value),
+				position: position + Vector::new(0.0, 4.0),
+				horizontal_alignment: alignment::Horizontal::Center,
+				size: 14.0,
+				..Text::default()
+			});
+		}
+	}
+
+	pub fn draw_frame(&self, frame: &mut Frame, title: &str, x_label: &str, y_label: &str) {
+		let stroke = Stroke::default().with_color(Color::from_rgb8(120, 120, 120));
+		let top_left = self.origin - Vector::new(0.0, self.size.height);
+		let bottom_right = self.origin + Vector::new(self.size.width, 0.0);
+		frame.stroke(&Path::line(self.origin, top_left), stroke);
+		frame.stroke(&Path::line(self.origin, bottom_right), stroke);
+
+		frame.fill_text(Text {
+			content: title.to_string(),
+			position: Point::new(self.origin.x + self.size.width / 2.0, MARGIN_TOP / 2.0),
+			horizontal_alignment: alignment::Horizontal::Center,
+			vertical_alignment: alignment::Vertical::Center,
+			..Text::default()
+		});
+		frame.fill_text(Text {
+			content: x_label.to_string(),
+			position: Point::new(
+				self.origin.x + self.size.width / 2.0,
+				self.origin.y + MARGIN_BOTTOM - 8.0,
+			),
+			horizontal_alignment: alignment::Horizontal::Center,
+			vertical_alignment: alignment::Vertical::Bottom,
+			..Text::default()
+		});
+		frame.fill_text(Text {
+			content: y_label.to_string(),
+			position: Point::new(4.0, MARGIN_TOP / 2.0),
+			vertical_alignment: alignment::Vertical::Center,
+			..Text::default()
+		});
+
+		for (value, position) in [(self.y_range.0, self.origin), (self.y_range.1, top_left)] {
+			frame.fill_text(Text {
+				content: format_tick(value),
+				position: position - Vector::new(4.0, 0.0),
+				horizontal_alignment: alignment::Horizontal::Right,
+				vertical_alignment: alignment::Vertical::Center,
+				size: 14.0,
+				..Text::default()
+			});
+		}
+	}
+}
+
+impl Program<Message> for LineChart {
+	type State = ();
+
+	fn draw(
+		&self, _state: &(), renderer: &Renderer, _theme: &Theme, bounds: Rectangle, _cursor: Cursor,
+	) -> Vec<Geometry> {
+		let mut frame = Frame::new(renderer, bounds.size());
+
+		let points = || {
+			self.series
+				.iter()
+				.flat_map(|series| series.points.iter())
+				.chain(&self.markers)
+		};
+		let x_range = range(points().map(|(x, _)| *x));
+		let y_range = range(points().map(|(_, y)| *y));
+		let plot = Plot::new(bounds.size(), x_range, y_range);
+		plot.draw_axes(&mut frame, &self.title, &self.x_label, &self.y_label);
+
+		for (i, series) in self.series.iter().enumerate() {
+			let stroke = Stroke::default().with_color(series.color).with_width(2.0);
+			let path = Path::new(|builder| {
+				let points = series.points.iter().filter(|(x, y)| x.is_finite() && y.is_finite());
+				for (j, (x, y)) in points.enumerate() {
+					if j == 0 {
+						builder.move_to(plot.point(*x, *y));
+					} else {
+						builder.line_to(plot.point(*x, *y));
+					}
+				}
+			});
+			frame.stroke(&path, stroke);
+
+			let legend = Point::new(plot.origin.x + 8.0, MARGIN_TOP + 4.0 + 18.0 * i as f32);
+			frame.fill_rectangle(legend + Vector::new(0.0, 4.0), Size::new(12.0, 4.0), series.color);
+			frame.fill_text(Text {
+				content: series.label.clone(),
+				position: legend + Vector::new(16.0, -2.0),
+				color: series.color,
+				size: 14.0,
+				..Text::default()
+			});
+		}
+
+		for (x, y) in &self.markers {
+			frame.fill(&Path::circle(plot.point(*x, *y), 4.0), Color::BLACK);
+		}
+
+		vec![frame.into_geometry()]
+	}
+}
+
+impl Program<Message> for BarChart {
+	type State = ();
+
+	fn draw(
+		&self, _state: &(), renderer: &Renderer, _theme: &Theme, bounds: Rectangle, _cursor: Cursor,
+	) -> Vec<Geometry> {
+		let mut frame = Frame::new(renderer, bounds.size());
+
+		let values = self
+			.series
+			.iter()
+			.flat_map(|series| series.points.iter().map(|(_, y)| *y));
+		let (low, high) = range(values.chain([0.0]));
+		let plot = Plot::new(bounds.size(), (0.0, self.categories.len().max(1) as f32), (low, high));
+		plot.draw_frame(&mut frame, &self.title, "", &self.y_label);
+
+		let width = 0.8 / self.series.len().max(1) as f32;
+		for (i, series) in self.series.iter().enumerate() {
+			for &(x, y) in &series.points {
+				if !y.is_finite() {
+					continue;
+				}
+				let left = x + 0.1 + i as f32 * width;
+				let top_left = plot.point(left, y.max(0.0));
+				let bottom_right = plot.point(left + width, y.min(0.0));
+				frame.fill_rectangle(
+					top_left,
+					Size::new(bottom_right.x - top_left.x, bottom_right.y - top_left.y),
+					series.color,
+				);
+			}
+
+			let legend = Point::new(plot.origin.x + 8.0, MARGIN_TOP + 4.0 + 18.0 * i as f32);
+			frame.fill_rectangle(legend + Vector::new(0.0, 4.0), Size::new(12.0, 4.0), series.color);
+			frame.fill_text(Text {
+				content: series.label.clone(),
+				position: legend + Vector::new(16.0, -2.0),
+				color: series.color,
+				size: 14.0,
+				..Text::default()
+			});
+		}
+
+		for (i, category) in self.categories.iter().enumerate() {
+			frame.fill_text(Text {
+				content: category.clone(),
+				position: plot.point(i as f32 + 0.5, plot.y_range.0) + Vector::new(0.0, 4.0),
+				horizontal_alignment: alignment::Horizontal::Center,
+				size: 14.0,
+				..Text::default()
+			});
+		}
+
+		vec![frame.into_geometry()]
+	}
+}
+
+impl WaterfallChart {
+	pub fn view(self) -> Canvas<WaterfallChart, Message> {
+		Canvas::new(self).width(Length::Fill).height(Length::Fill)
+	}
+}
+
+impl Program<Message> for WaterfallChart {
+	type State = ();
+
+	fn draw(
+		&self, _state: &(), renderer: &Renderer, _theme: &Theme, bounds: Rectangle, _cursor: Cursor,
+	) -> Vec<Geometry> {
+		let mut frame = Frame::new(renderer, bounds.size());
+
+		let (low, high) = range(self.steps.iter().map(|(_, value, _)| *value).chain([0.0]));
+		let plot = Plot::new(bounds.size(), (0.0, self.steps.len().max(1) as f32), (low, high));
+		plot.draw_frame(&mut frame, &self.title, "", &self.y_label);
+
+		let mut level = 0.0;
+		for (i, (label, value, total)) in self.steps.iter().enumerate() {
+			let (from, to, color) = if *total {
+				level = *value;
+				(0.0, *value, PALETTE[0])
+			} else {
+				level -= value;
+				(level.min(level + value), level.max(level + value), PALETTE[1])
+			};
+			let top_left = plot.point(i as f32 + 0.15, to);
+			let bottom_right = plot.point(i as f32 + 0.85, from);
+			frame.fill_rectangle(
+				top_left,
+				Size::new(bottom_right.x - top_left.x, bottom_right.y - top_left.y),
+				color,
+			);
+			frame.fill_text(Text {
+				content: format!("{:.1}", value),
+				position: top_left + Vector::new((bottom_right.x - top_left.x) / 2.0, -4.0),
+				horizontal_alignment: alignment::Horizontal::Center,
+				vertical_alignment: alignment::Vertical::Bottom,
+				size: 12.0,
+				..Text::default()
+			});
+			frame.fill_text(Text {
+				content: label.clone(),
+				position: plot.point(i as f32 + 0.5, plot.y_range.0) + Vector::new(0.0, 4.0),
+				horizontal_alignment: alignment::Horizontal::Center,
+				size: 14.0,
+				..Text::default()
+			});
+		}
+
+		vec![frame.into_geometry()]
+	}
+}
+
+impl Heatmap {
+	pub fn view(self) -> Canvas<Heatmap, Message> {
+		Canvas::new(self).width(Length::Fill).height(Length::Fill)
+	}
+}
+
+impl Program<Message> for Heatmap {
+	type State = ();
+
+	fn draw(
+		&self, _state: &(), renderer: &Renderer, _theme: &Theme, bounds: Rectangle, _cursor: Cursor,
+	) -> Vec<Geometry> {
+		let mut frame = Frame::new(renderer, bounds.size());
+
+		let half_step = |values: &[f32]| match values {
+			[first, second, ..] => (second - first) / 2.0,
+			_ => 0.5,
+		};
+		let (x_step, y_step) = (half_step(&self.x), half_step(&self.y));
+		let x_range = range(self.x.iter().copied());
+		let y_range = range(self.y.iter().copied());
+		let plot = Plot::new(
+			bounds.size(),
+			(x_range.0 - x_step, x_range.1 + x_step),
+			(y_range.0 - y_step, y_range.1 + y_step),
+		);
+		plot.draw_axes(&mut frame, &self.title, &self.x_label, &self.y_label);
+
+		let (low, high) = widen(range(self.values.iter().flatten().copied()));
+		for (x, column) in self.x.iter().zip(&self.values) {
+			for (y, value) in self.y.iter().zip(column) {
+				let top_left = plot.point(x - x_step, y + y_step);
+				let bottom_right = plot.point(x + x_step, y - y_step);
+				let size = Size::new(bottom_right.x - top_left.x, bottom_right.y - top_left.y);
+				let t = (value - low) / (high - low);
+				frame.fill_rectangle(top_left, size, heat(t));
+
+				if size.width > 40.0 && size.height > 16.0 {
+					frame.fill_text(Text {
+						content: format_tick(*value),
+						position: top_left + Vector::new(size.width / 2.0, size.height / 2.0),
+						color: if t > 0.5 { Color::WHITE } else { Color::BLACK },
+						horizontal_alignment: alignment::Horizontal::Center,
+						vertical_alignment: alignment::Vertical::Center,
+						size: 12.0,
+						..Text::default()
+					});
+				}
+			}
+		}
+
+		if let Some((x, y)) = self.marker {
+			let stroke = Stroke::default().with_color(Color::BLACK).with_width(2.0);
+			frame.stroke(&Path::circle(plot.point(x, y), 5.0), stroke);
+		}
+
+		vec![frame.into_geometry()]
+	}
+}
+
+pub fn range(values: impl Iterator<Item = f32>) -> (f32, f32) {
+	values
+		.filter(|value| value.is_finite())
+		.fold(None, |range: Option<(f32, f32)>, value| match range {
+			Some((min, max)) => Some((min.min(value), max.max(value))),
+			None => Some((value, value)),
+		})
+		.unwrap_or((0.0, 1.0))
+}
+
+fn widen(range: (f32, f32)) -> (f32, f32) {
+	if range.1 - range.0 > f32::EPSILON {
+		range
+	} else {
+		(range.0 - 0.5, range.1 + 0.5)
+	}
+}
+
+fn heat(t: f32) -> Color {
+	let cold = Color::from_rgb(0.95, 0.95, 0.95);
+	let hot = PALETTE[0];
+	Color::from_rgb(
+		cold.r + (hot.r - cold.r) * t,
+		cold.g + (hot.g - cold.g) * t,
+		cold.b + (hot.b - cold.b) * t,
+	)
+}
+
+pub fn format_tick(value: f32) -> String {
+	if value.abs() >= 1000.0 || value.fract() == 0.0 {
+		format!("{:.0}", value)
+	} else {
+		format!("{:.3}", value)
+	}
+}
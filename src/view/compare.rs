@@ -0,0 +1,136 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+use std::fmt;
+use std::fmt::{Display, Formatter};
+
+use iced::{Alignment, Element, Length};
+use iced::widget::{button, column, container, pick_list, row, text, text_input, Row};
+
+use crate::cost::format_cost;
+use crate::scenario::{Scenario, Summary};
+use crate::view::calculator::Message;
+use crate::view::table::table;
+use crate::view::wafer::WaferViewState;
+use crate::wafer::WaferMap;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct ScenarioOption {
+	index: usize,
+	name: String,
+}
+
+impl Display for ScenarioOption {
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		f.write_str(&self.name)
+	}
+}
+
+pub fn scenarios(scenarios: &[Scenario], active: usize) -> Element<'static, Message> {
+	let tabs = Row::with_children(
+		scenarios
+			.iter()
+			.enumerate()
+			.map(|(i, scenario)| {
+				let tab = button(text(&scenario.name));
+				if i == active {
+					tab.into()
+				} else {
+					tab.on_press(Message::Scenario(i)).into()
+				}
+			})
+			.collect(),
+	)
+	.spacing(4);
+
+	let mut remove = button(text("Remove"));
+	if scenarios.len() > 1 {
+		remove = remove.on_press(Message::RemoveScenario);
+	}
+
+	row![
+		tabs,
+		button(text("Duplicate")).on_press(Message::AddScenario),
+		remove,
+		text_input("Scenario Name", &scenarios[active].name)
+			.on_input(Message::ScenarioName)
+			.width(Length::Fixed(200.0)),
+	]
+	.spacing(16)
+	.align_items(Alignment::Center)
+	.into()
+}
+
+pub fn compare<'a>(
+	scenarios: &'a [Scenario], compared: (usize, usize), maps: &'a (WaferMap, WaferMap),
+	views: &'a (WaferViewState, WaferViewState),
+) -> Element<'a, Message> {
+	let options: Vec<ScenarioOption> = scenarios
+		.iter()
+		.enumerate()
+		.map(|(index, scenario)| ScenarioOption {
+			index,
+			name: scenario.name.clone(),
+		})
+		.collect();
+	let (left, right) = (&scenarios[compared.0], &scenarios[compared.1]);
+
+	let pickers = row![
+		pick_list(options.clone(), Some(options[compared.0].clone()), |option| {
+			Message::CompareLeft(option.index)
+		})
+		.width(Length::Fill),
+		pick_list(options.clone(), Some(options[compared.1].clone()), |option| {
+			Message::CompareRight(option.index)
+		})
+		.width(Length::Fill),
+	]
+	.spacing(16);
+
+	let canvases = row![views.0.view(&left.wafer, &maps.0), views.1.view(&right.wafer, &maps.1),].spacing(16);
+
+	let a = Summary::new(&left.wafer, &maps.0);
+	let b = Summary::new(&right.wafer, &maps.1);
+	let count = |label: &str, before: usize, after: usize| {
+		vec![
+			label.to_string(),
+			before.to_string(),
+			after.to_string(),
+			format!("{:+}", after as i64 - before as i64),
+		]
+	};
+	let rows = vec![
+		count("Complete Dies", a.complete, b.complete),
+		count("Partial Dies", a.partial, b.partial),
+		count("Wasted Dies", a.wasted, b.wasted),
+		count("Defective Dies", a.defective, b.defective),
+		count("Good Dies", a.good, b.good),
+		vec![
+			String::from("Die Yield"),
+			format!("{:.2}%", a.die_yield * 100.0),
+			format!("{:.2}%", b.die_yield * 100.0),
+			format!("{:+.2}%", (b.die_yield - a.die_yield) * 100.0),
+		],
+		vec![
+			String::from("Cost / Good Die"),
+			format_cost(a.cost_per_good_die),
+			format_cost(b.cost_per_good_die),
+			format_delta_cost(b.cost_per_good_die - a.cost_per_good_die),
+		],
+	];
+	let deltas = table(&["", left.name.as_str(), right.name.as_str(), "Δ"], rows);
+
+	let content = column![pickers, canvases, deltas].spacing(16).padding(8);
+	container(content).width(Length::Fill).height(Length::Fill).into()
+}
+
+fn format_delta_cost(delta: f32) -> String {
+	if delta.is_finite() {
+		format!("{:+.2}", delta)
+	} else {
+		String::from("N/A")
+	}
+}
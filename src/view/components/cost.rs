@@ -0,0 +1,48 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+use iced::widget::{column, container, text};
+use iced_aw::{grid_row, GridRow, NumberInput};
+
+use crate::cost::{Cost, MAXIMUM_COST};
+use crate::view::calculator::{Component, Message};
+use crate::view::ROW_HEIGHT;
+
+pub fn cost(cost: Cost) -> GridRow<'static, Message> {
+	let wafer_label = container(text("Wafer")).height(ROW_HEIGHT).center_y();
+	let test_label = container(text("Test per Die")).height(ROW_HEIGHT).center_y();
+	let packaging_label = container(text("Packaging per Unit")).height(ROW_HEIGHT).center_y();
+	let labels = column![wafer_label, test_label, packaging_label];
+
+	let wafer_input = container(
+		NumberInput::new(cost.wafer, MAXIMUM_COST, Message::number_input(Component::WaferCost))
+			.min(0.0)
+			.step(100.0),
+	)
+	.height(ROW_HEIGHT)
+	.center_y();
+	let test_input = container(
+		NumberInput::new(cost.test, MAXIMUM_COST, Message::number_input(Component::TestCost))
+			.min(0.0)
+			.step(0.1),
+	)
+	.height(ROW_HEIGHT)
+	.center_y();
+	let packaging_input = container(
+		NumberInput::new(
+			cost.packaging,
+			MAXIMUM_COST,
+			Message::number_input(Component::PackagingCost),
+		)
+		.min(0.0)
+		.step(0.1),
+	)
+	.height(ROW_HEIGHT)
+	.center_y();
+	let inputs = column![wafer_input, test_input, packaging_input];
+
+	grid_row![container(text("Cost ($)")).center_y(), labels, inputs]
+}
@@ -0,0 +1,60 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+use iced::widget::{checkbox, container, scrollable, text, Column, Row};
+use iced::widget::scrollable::{Direction, Properties};
+use iced_aw::{grid_row, GridRow};
+
+use crate::view::calculator::Message;
+use crate::view::ROW_HEIGHT;
+use crate::wafer::Wafer;
+
+pub const MAXIMUM_FIELD_POSITIONS: u32 = 256;
+const POSITIONS_SIZE: f32 = 240.0;
+
+pub fn reticle_defects(wafer: &Wafer) -> GridRow<'static, Message> {
+	let (columns, rows) = wafer.dies_per_field();
+	let label = container(text(format!("Reticle Defects ({} × {})", columns, rows)))
+		.height(ROW_HEIGHT)
+		.center_y();
+	if columns * rows > MAXIMUM_FIELD_POSITIONS {
+		let note = container(text(format!(
+			"Marking is limited to {} positions per field",
+			MAXIMUM_FIELD_POSITIONS
+		)))
+		.height(ROW_HEIGHT)
+		.center_y();
+		return grid_row![label, note];
+	}
+
+	let positions = Column::with_children(
+		(0..rows)
+			.map(|y| {
+				Row::with_children(
+					(0..columns)
+						.map(|x| {
+							checkbox("", wafer.reticle_defects.contains(&(x, y)), move |b| {
+								Message::ReticleDefect((x, y), b)
+							})
+							.into()
+						})
+						.collect(),
+				)
+				.into()
+			})
+			.collect(),
+	);
+
+	let positions = scrollable(positions).direction(Direction::Both {
+		vertical: Properties::default(),
+		horizontal: Properties::default(),
+	});
+	let positions = container(positions)
+		.max_width(POSITIONS_SIZE)
+		.max_height(POSITIONS_SIZE);
+
+	grid_row![label, positions]
+}
@@ -4,12 +4,26 @@
  * file, You can obtain one at http://mozilla.org/MPL/2.0/.
  */
 
+pub use cost::*;
+pub use defects::*;
+pub use prober::*;
+pub use reticle::*;
 pub use scribe::*;
+pub use secondary::*;
 pub use simple::*;
 pub use size::*;
+pub use stagger::*;
+pub use stitching::*;
 pub use translate::*;
 
+mod cost;
+mod defects;
+mod prober;
+mod reticle;
 mod scribe;
+mod secondary;
 mod simple;
 mod size;
+mod stagger;
+mod stitching;
 mod translate;
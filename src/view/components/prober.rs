@@ -0,0 +1,46 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+use iced::widget::{button, checkbox, column, container, pick_list, row, text};
+use iced_aw::{grid_row, GridRow};
+
+use crate::file::{IndexOrigin, Notch, ProberConvention, SiteFormat};
+use crate::view::calculator::{Component, Message};
+use crate::view::ROW_HEIGHT;
+
+pub fn site_list(convention: ProberConvention) -> GridRow<'static, Message> {
+	let label = container(text("Site List")).height(ROW_HEIGHT).center_y();
+
+	let origin = container(pick_list(
+		IndexOrigin::ALL,
+		Some(convention.origin),
+		Message::IndexOrigin,
+	))
+	.height(ROW_HEIGHT)
+	.center_y();
+	let notch = container(pick_list(Notch::ALL, Some(convention.notch), Message::Notch))
+		.height(ROW_HEIGHT)
+		.center_y();
+
+	let x_right = checkbox("X Right", convention.x_right, Message::checkbox(Component::XRight));
+	let y_up = checkbox("Y Up", convention.y_up, Message::checkbox(Component::YUp));
+	let axes = column![
+		container(x_right).height(ROW_HEIGHT).center_y(),
+		container(y_up).height(ROW_HEIGHT).center_y(),
+	];
+
+	let export = row![
+		button(text("CSV")).on_press(Message::ExportSites(SiteFormat::Csv)),
+		button(text("JSON")).on_press(Message::ExportSites(SiteFormat::Json)),
+	]
+	.spacing(4);
+
+	grid_row![
+		column![label, container(export).height(ROW_HEIGHT).center_y()],
+		column![origin, notch],
+		axes,
+	]
+}
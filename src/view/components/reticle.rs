@@ -0,0 +1,53 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+use iced::widget::{column, container, pick_list, text};
+use iced_aw::{grid_row, GridRow, NumberInput};
+
+use crate::view::calculator::{Component, Message};
+use crate::view::ROW_HEIGHT;
+use crate::wafer::{MAXIMUM_RETICLE_DIMENSION, MINIMUM_DIE_DIMENSION, Reticle, ReticleOption};
+
+pub fn reticle(reticle: Reticle) -> GridRow<'static, Message> {
+	let label = container(text("Reticle")).height(ROW_HEIGHT).center_y();
+	let picker = container(pick_list(
+		ReticleOption::ALL,
+		Some(reticle.into()),
+		Message::ReticleOption,
+	))
+	.height(ROW_HEIGHT)
+	.center_y();
+
+	match reticle {
+		Reticle::Custom { width, height } => {
+			let width_input = container(
+				NumberInput::new(
+					width,
+					MAXIMUM_RETICLE_DIMENSION,
+					Message::number_input(Component::ReticleWidth),
+				)
+				.min(MINIMUM_DIE_DIMENSION)
+				.step(0.5),
+			)
+			.height(ROW_HEIGHT)
+			.center_y();
+			let height_input = container(
+				NumberInput::new(
+					height,
+					MAXIMUM_RETICLE_DIMENSION,
+					Message::number_input(Component::ReticleHeight),
+				)
+				.min(MINIMUM_DIE_DIMENSION)
+				.step(0.5),
+			)
+			.height(ROW_HEIGHT)
+			.center_y();
+
+			grid_row![label, picker, column![width_input, height_input]]
+		}
+		_ => grid_row![label, picker],
+	}
+}
@@ -4,7 +4,7 @@
  * file, You can obtain one at http://mozilla.org/MPL/2.0/.
  */
 
-use iced::widget::{checkbox, column, container, text};
+use iced::widget::{checkbox, column, container, text, Container};
 use iced_aw::{grid_row, GridRow, NumberInput};
 
 use crate::view::calculator::{Component, Message};
@@ -14,34 +14,26 @@ use crate::wafer::{MAXIMUM_SCRIBE_WIDTH, Wafer};
 pub fn scribe_lines(wafer: &Wafer, equal_scribe: bool) -> GridRow<'static, Message> {
 	let horizontal_label = container(text("Horizontal")).height(ROW_HEIGHT).center_y();
 	let vertical_label = container(text("Vertical")).height(ROW_HEIGHT).center_y();
-	let labels = column![horizontal_label, vertical_label];
+	let field_horizontal_label = container(text("Field Horizontal")).height(ROW_HEIGHT).center_y();
+	let field_vertical_label = container(text("Field Vertical")).height(ROW_HEIGHT).center_y();
+	let labels = column![
+		horizontal_label,
+		vertical_label,
+		field_horizontal_label,
+		field_vertical_label
+	];
 
-	let horizontal_input = container(
-		NumberInput::new(
-			wafer.scribe_lanes.0,
-			MAXIMUM_SCRIBE_WIDTH,
-			Message::number_input(Component::ScribeHorizontal),
-		)
-		.step(0.2),
-	)
-	.center_y();
+	let horizontal_input = scribe_input(wafer.scribe_lanes.0, false, Component::ScribeHorizontal);
+	let vertical_input = scribe_input(wafer.scribe_lanes.1, equal_scribe, Component::ScribeVertical);
+	let field_horizontal_input = scribe_input(wafer.field_scribe_lanes.0, false, Component::FieldScribeHorizontal);
+	let field_vertical_input = scribe_input(wafer.field_scribe_lanes.1, equal_scribe, Component::FieldScribeVertical);
 
-	let vertical_input = container(
-		NumberInput::new(
-			wafer.scribe_lanes.1,
-			if equal_scribe {
-				wafer.scribe_lanes.1
-			} else {
-				MAXIMUM_SCRIBE_WIDTH
-			},
-			Message::number_input(Component::ScribeVertical),
-		)
-		.min(if equal_scribe { wafer.scribe_lanes.1 } else { 0.0 })
-		.step(0.2),
-	)
-	.center_y();
-
-	let inputs = column![horizontal_input, vertical_input];
+	let inputs = column![
+		horizontal_input,
+		vertical_input,
+		field_horizontal_input,
+		field_vertical_input
+	];
 
 	grid_row![
 		container(text("Scribe Lines (mm)")).center_y(),
@@ -50,3 +42,17 @@ pub fn scribe_lines(wafer: &Wafer, equal_scribe: bool) -> GridRow<'static, Messa
 		checkbox("", equal_scribe, Message::checkbox(Component::ScribeHorizontal)),
 	]
 }
+
+fn scribe_input(value: f32, locked: bool, component: Component) -> Container<'static, Message> {
+	container(
+		NumberInput::new(
+			value,
+			if locked { value } else { MAXIMUM_SCRIBE_WIDTH },
+			Message::number_input(component),
+		)
+		.min(if locked { value } else { 0.0 })
+		.step(0.2),
+	)
+	.height(ROW_HEIGHT)
+	.center_y()
+}
@@ -0,0 +1,61 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+use iced::widget::{checkbox, column, container, text};
+use iced_aw::{grid_row, GridRow, NumberInput};
+
+use crate::view::calculator::{Component, Message};
+use crate::view::ROW_HEIGHT;
+use crate::wafer::{Wafer, MAXIMUM_SECONDARY_SITES, MINIMUM_DIE_DIMENSION};
+
+pub fn secondary_die(wafer: &Wafer) -> GridRow<'static, Message> {
+	let secondary = wafer.secondary;
+	let check = checkbox(
+		"Secondary Die",
+		secondary.enabled,
+		Message::checkbox(Component::Secondary),
+	);
+	if !secondary.enabled {
+		return grid_row![container(check).height(ROW_HEIGHT).center_y()];
+	}
+
+	let width_label = container(text("Width (mm)")).height(ROW_HEIGHT).center_y();
+	let height_label = container(text("Height (mm)")).height(ROW_HEIGHT).center_y();
+	let labels = column![width_label, height_label];
+
+	let limit = wafer.die.width().max(wafer.die.height());
+	let width_input = container(
+		NumberInput::new(secondary.width, limit, Message::number_input(Component::SecondaryWidth))
+			.min(MINIMUM_DIE_DIMENSION)
+			.step(0.1),
+	)
+	.height(ROW_HEIGHT)
+	.center_y();
+	let height_input = container(
+		NumberInput::new(
+			secondary.height,
+			limit,
+			Message::number_input(Component::SecondaryHeight),
+		)
+		.min(MINIMUM_DIE_DIMENSION)
+		.step(0.1),
+	)
+	.height(ROW_HEIGHT)
+	.center_y();
+	let mut inputs = column![width_input, height_input];
+	if wafer.secondary_sites() > MAXIMUM_SECONDARY_SITES {
+		inputs = inputs.push(
+			container(text(format!(
+				"Too small to place: over {} sites",
+				MAXIMUM_SECONDARY_SITES
+			)))
+			.height(ROW_HEIGHT)
+			.center_y(),
+		);
+	}
+
+	grid_row![check, labels, inputs]
+}
@@ -4,22 +4,25 @@
  * file, You can obtain one at http://mozilla.org/MPL/2.0/.
  */
 
-use iced::widget::{checkbox, container, pick_list, text};
+use iced::widget::{checkbox, column, container, pick_list, text};
 use iced_aw::{grid_row, GridRow, NumberInput};
 
 use crate::view::calculator::{Component, Message};
 use crate::view::ROW_HEIGHT;
-use crate::wafer::{Diameter, Panel, Shape, ShapeOption, Wafer, YieldModel};
+use crate::wafer::{
+	Diameter, MAXIMUM_CLUSTER_PARAMETER, MAXIMUM_DEFECT_RATE, MAXIMUM_EDGE_LOSS, MINIMUM_CLUSTER_PARAMETER, Panel,
+	Shape, ShapeOption, Wafer, YieldModel,
+};
 
 pub fn critical_area(wafer: &Wafer, simple: bool) -> GridRow<'static, Message> {
 	let label = container(text("Critical Area (mm²)")).height(ROW_HEIGHT).center_y();
 	let input = container(
 		NumberInput::new(
 			wafer.critical_area,
-			wafer.die.area(),
+			wafer.usable_area(),
 			Message::number_input(Component::CriticalArea),
 		)
-		.min(if simple { wafer.die.area() } else { 0.0 })
+		.min(if simple { wafer.usable_area() } else { 0.0 })
 		.step(0.5),
 	)
 	.height(ROW_HEIGHT)
@@ -48,9 +51,13 @@ pub fn shape(shape: Shape) -> GridRow<'static, Message> {
 pub fn defect_rate(defect_rate: f32) -> GridRow<'static, Message> {
 	let label = container(text("Defect Rate (#/cm²)")).height(ROW_HEIGHT).center_y();
 	let input = container(
-		NumberInput::new(defect_rate, 10000.0, Message::number_input(Component::DefectRate))
-			.min(0.0)
-			.step(0.05),
+		NumberInput::new(
+			defect_rate,
+			MAXIMUM_DEFECT_RATE,
+			Message::number_input(Component::DefectRate),
+		)
+		.min(0.0)
+		.step(0.05),
 	)
 	.height(ROW_HEIGHT)
 	.center_y();
@@ -61,7 +68,7 @@ pub fn defect_rate(defect_rate: f32) -> GridRow<'static, Message> {
 pub fn edge_loss(edge_loss: f32) -> GridRow<'static, Message> {
 	let label = container(text("Edge Loss (mm)")).height(ROW_HEIGHT).center_y();
 	let input = container(
-		NumberInput::new(edge_loss, 25.0, Message::number_input(Component::EdgeLoss))
+		NumberInput::new(edge_loss, MAXIMUM_EDGE_LOSS, Message::number_input(Component::EdgeLoss))
 			.min(0.0)
 			.step(0.2),
 	)
@@ -78,11 +85,43 @@ pub fn die_centering(centered: bool) -> GridRow<'static, Message> {
 	grid_row![label, checkbox]
 }
 
-pub fn yield_model(yield_model: YieldModel) -> GridRow<'static, Message> {
+pub fn yield_model(yield_model: YieldModel, cluster: f32) -> GridRow<'static, Message> {
 	let label = container(text("Yield Model")).height(ROW_HEIGHT).center_y();
 	let picker = container(pick_list(YieldModel::ALL, Some(yield_model), Message::YieldModel))
 		.height(ROW_HEIGHT)
 		.center_y();
 
-	grid_row![label, picker]
+	if yield_model != YieldModel::NegativeBinomial {
+		return grid_row![label, picker];
+	}
+
+	let cluster_label = container(text("Cluster Parameter (α)")).height(ROW_HEIGHT).center_y();
+	let cluster_input = container(
+		NumberInput::new(
+			cluster,
+			MAXIMUM_CLUSTER_PARAMETER,
+			Message::number_input(Component::ClusterParameter),
+		)
+		.min(MINIMUM_CLUSTER_PARAMETER)
+		.step(0.1),
+	)
+	.height(ROW_HEIGHT)
+	.center_y();
+
+	grid_row![label, picker, column![cluster_label, cluster_input]]
+}
+
+pub fn labelled_number(
+	label: &str, value: f32, bounds: (f32, f32), step: f32, component: Component,
+) -> GridRow<'static, Message> {
+	let label = container(text(label)).height(ROW_HEIGHT).center_y();
+	let input = container(
+		NumberInput::new(value, bounds.1, Message::number_input(component))
+			.min(bounds.0)
+			.step(step),
+	)
+	.height(ROW_HEIGHT)
+	.center_y();
+
+	grid_row![label, input]
 }
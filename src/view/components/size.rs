@@ -7,7 +7,7 @@
 use iced::widget::{checkbox, column, container, text};
 use iced_aw::{grid_row, GridRow, NumberInput};
 
-use crate::die::Die;
+use crate::die::{Die, MAXIMUM_DIE_BORDER};
 use crate::view::calculator::{Component, Message};
 use crate::view::ROW_HEIGHT;
 use crate::wafer::Wafer;
@@ -18,29 +18,69 @@ pub fn die_size(wafer: &Wafer, reticle_limit: bool) -> GridRow<'static, Message>
 	let labels = column![width_label, height_label];
 
 	let width_input = container(
-		NumberInput::new(wafer.die.width(), 0.0, Message::number_input(Component::DieWidth))
-			.bounds(wafer.die.width_bounds(reticle_limit, wafer.shape))
-			.step(0.2),
+		NumberInput::new(
+			wafer.die.active_width(),
+			0.0,
+			Message::number_input(Component::DieWidth),
+		)
+		.bounds(wafer.die.width_bounds(reticle_limit, wafer.reticle, wafer.shape))
+		.step(0.2),
 	)
 	.height(ROW_HEIGHT)
 	.center_y();
 
 	let height_input = container(
-		NumberInput::new(wafer.die.height(), 0.0, Message::number_input(Component::DieHeight))
-			.bounds(wafer.die.height_bounds(reticle_limit, wafer.shape))
-			.step(0.2),
+		NumberInput::new(
+			wafer.die.active_height(),
+			0.0,
+			Message::number_input(Component::DieHeight),
+		)
+		.bounds(wafer.die.height_bounds(reticle_limit, wafer.reticle, wafer.shape))
+		.step(0.2),
 	)
 	.height(ROW_HEIGHT)
 	.center_y();
 
 	let inputs = column![width_input, height_input];
 
-	let square = checkbox(
-		"Square",
-		matches!(wafer.die, Die::Square(_)),
-		Message::checkbox(Component::DieWidth),
-	);
+	let square = checkbox("Square", wafer.die.is_square(), Message::checkbox(Component::DieWidth));
 	let reticle = checkbox("Reticle Limit", reticle_limit, Message::checkbox(Component::Reticle));
 
 	grid_row![labels, inputs, square, reticle]
 }
+
+pub fn die_border(die: Die) -> GridRow<'static, Message> {
+	let seal_ring_label = container(text("Seal Ring (mm)")).height(ROW_HEIGHT).center_y();
+	let keep_out_label = container(text("Die Keep-out (mm)")).height(ROW_HEIGHT).center_y();
+	let labels = column![seal_ring_label, keep_out_label];
+
+	let seal_ring_input = container(
+		NumberInput::new(
+			die.seal_ring,
+			MAXIMUM_DIE_BORDER,
+			Message::number_input(Component::SealRing),
+		)
+		.min(0.0)
+		.step(0.01),
+	)
+	.height(ROW_HEIGHT)
+	.center_y();
+	let keep_out_input = container(
+		NumberInput::new(
+			die.keep_out,
+			MAXIMUM_DIE_BORDER,
+			Message::number_input(Component::DieKeepOut),
+		)
+		.min(0.0)
+		.step(0.01),
+	)
+	.height(ROW_HEIGHT)
+	.center_y();
+	let inputs = column![seal_ring_input, keep_out_input];
+
+	let footprint = container(text(format!("Footprint\n{:.2} × {:.2} mm", die.width(), die.height())))
+		.height(ROW_HEIGHT)
+		.center_y();
+
+	grid_row![labels, inputs, footprint]
+}
@@ -0,0 +1,71 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+use iced::Alignment;
+use iced::widget::{button, column, container, pick_list, row, text, Column};
+use iced_aw::{grid_row, GridRow, NumberInput};
+
+use crate::view::calculator::{Component, Message};
+use crate::view::ROW_HEIGHT;
+use crate::wafer::{Stagger, StaggerOption, Wafer, MAXIMUM_STAGGER};
+
+pub fn stagger(wafer: &Wafer) -> GridRow<'static, Message> {
+	let stagger = &wafer.stagger;
+	let label = container(text("Row Stagger")).height(ROW_HEIGHT).center_y();
+
+	let picker = container(pick_list(
+		StaggerOption::ALL,
+		Some(stagger.into()),
+		Message::StaggerOption,
+	))
+	.height(ROW_HEIGHT)
+	.center_y();
+
+	let offset_label = if wafer.dies_per_field().1 > 1 {
+		"Offset per Field Row (× Die Pitch)"
+	} else {
+		"Offset (× Die Pitch)"
+	};
+	let offset_label = container(text(offset_label)).height(ROW_HEIGHT).center_y();
+	match stagger {
+		Stagger::None => grid_row![label, picker],
+		Stagger::PerRow(offsets) => {
+			let mut rows = Column::new().push(offset_label);
+			for (i, offset) in offsets.iter().enumerate() {
+				rows = rows.push(
+					row![
+						text(format!("Row {}", i)),
+						NumberInput::new(*offset, MAXIMUM_STAGGER, Message::number_input(Component::RowOffset(i)),)
+							.min(0.0)
+							.step(0.05),
+						button(text("Remove")).on_press(Message::RemoveRowOffset(i)),
+					]
+					.spacing(8)
+					.height(ROW_HEIGHT)
+					.align_items(Alignment::Center),
+				);
+			}
+			let rows = rows.push(button(text("Add Row")).on_press(Message::AddRowOffset));
+
+			grid_row![label, picker, rows]
+		}
+		_ => {
+			let offset_input = container(
+				NumberInput::new(
+					stagger.fraction(),
+					MAXIMUM_STAGGER,
+					Message::number_input(Component::Stagger),
+				)
+				.min(0.0)
+				.step(0.05),
+			)
+			.height(ROW_HEIGHT)
+			.center_y();
+
+			grid_row![label, picker, column![offset_label, offset_input]]
+		}
+	}
+}
@@ -0,0 +1,60 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+use iced::widget::{checkbox, column, container, text};
+use iced_aw::{grid_row, GridRow, NumberInput};
+
+use crate::view::calculator::{Component, Message};
+use crate::view::ROW_HEIGHT;
+use crate::wafer::{MAXIMUM_STITCH_KEEP_OUT, Wafer};
+
+pub fn stitching(wafer: &Wafer) -> GridRow<'static, Message> {
+	let stitching = wafer.stitching;
+	let check = checkbox("Stitching", stitching.enabled, Message::checkbox(Component::Stitching));
+	if !stitching.enabled {
+		return grid_row![container(check).height(ROW_HEIGHT).center_y()];
+	}
+
+	let exposures = wafer.exposures();
+	let summary = container(text(format!(
+		"{} × {} Exposures\n{} Stitches",
+		exposures.columns,
+		exposures.rows,
+		exposures.stitches()
+	)))
+	.height(ROW_HEIGHT)
+	.center_y();
+
+	let loss_label = container(text("Loss per Stitch (%)")).height(ROW_HEIGHT).center_y();
+	let keep_out_label = container(text("Keep-out (mm)")).height(ROW_HEIGHT).center_y();
+	let labels = column![loss_label, keep_out_label];
+
+	let loss_input = container(
+		NumberInput::new(
+			stitching.yield_loss,
+			100.0,
+			Message::number_input(Component::StitchYieldLoss),
+		)
+		.min(0.0)
+		.step(0.1),
+	)
+	.height(ROW_HEIGHT)
+	.center_y();
+	let keep_out_input = container(
+		NumberInput::new(
+			stitching.keep_out,
+			MAXIMUM_STITCH_KEEP_OUT,
+			Message::number_input(Component::StitchKeepOut),
+		)
+		.min(0.0)
+		.step(0.05),
+	)
+	.height(ROW_HEIGHT)
+	.center_y();
+	let inputs = column![loss_input, keep_out_input];
+
+	grid_row![column![check, summary], labels, inputs]
+}
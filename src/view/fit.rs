@@ -0,0 +1,81 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+use iced::{Alignment, Element, Length};
+use iced::widget::{button, column, container, row, scrollable, text, Column};
+
+use crate::analysis::{Fit, Sample};
+use crate::view::calculator::Message;
+
+const CELL_WIDTH: Length = Length::Fixed(120.0);
+
+pub fn model_fit(samples: &[Sample], fits: &[Fit], measured: bool) -> Element<'static, Message> {
+	let header = row![
+		text("Wafer").width(Length::Fixed(200.0)),
+		text("Area (mm²)").width(CELL_WIDTH),
+		text("Tested").width(CELL_WIDTH),
+		text("Good").width(CELL_WIDTH),
+		text("Yield").width(CELL_WIDTH),
+	]
+	.spacing(8);
+	let mut sample_rows = Column::new().spacing(4).push(header);
+	for (i, sample) in samples.iter().enumerate() {
+		sample_rows = sample_rows.push(
+			row![
+				text(&sample.name).width(Length::Fixed(200.0)),
+				text(format!("{:.2}", sample.area)).width(CELL_WIDTH),
+				text(sample.tested.to_string()).width(CELL_WIDTH),
+				text(sample.good.to_string()).width(CELL_WIDTH),
+				text(format!("{:.2}%", sample.measured_yield() * 100.0)).width(CELL_WIDTH),
+				button(text("Remove")).on_press(Message::RemoveSample(i)),
+			]
+			.spacing(8)
+			.align_items(Alignment::Center),
+		);
+	}
+	let mut add = button(text("Add Probe Map"));
+	if measured {
+		add = add.on_press(Message::AddSample);
+	}
+	let sample_rows = sample_rows.push(add);
+
+	let header = row![
+		text("Model").width(Length::Fixed(200.0)),
+		text("D0 (#/cm²)").width(CELL_WIDTH),
+		text("α").width(CELL_WIDTH),
+		text("Log Likelihood").width(CELL_WIDTH),
+		text("AIC").width(CELL_WIDTH),
+		text("χ²").width(CELL_WIDTH),
+		text("RMSE").width(CELL_WIDTH),
+	]
+	.spacing(8);
+	let mut results = Column::new().spacing(4).push(header);
+	for fit in fits {
+		results = results.push(
+			row![
+				text(fit.model.to_string()).width(Length::Fixed(200.0)),
+				text(format!("{:.4}", fit.defect_rate)).width(CELL_WIDTH),
+				text(format!("{:.3}", fit.cluster)).width(CELL_WIDTH),
+				text(format!("{:.2}", fit.log_likelihood)).width(CELL_WIDTH),
+				text(format!("{:.2}", fit.aic)).width(CELL_WIDTH),
+				text(format!("{:.3}", fit.chi_squared)).width(CELL_WIDTH),
+				text(format!("{:.4}", fit.rmse)).width(CELL_WIDTH),
+				button(text("Apply")).on_press(Message::ApplyFit(fit.model)),
+			]
+			.spacing(8)
+			.align_items(Alignment::Center),
+		);
+	}
+
+	let content = column![
+		scrollable(sample_rows).height(Length::Fixed(240.0)),
+		text("Fits are ranked by AIC. A single die area cannot separate D0 from α."),
+		results,
+	]
+	.spacing(16)
+	.padding(8);
+	container(content).width(Length::Fill).height(Length::Fill).into()
+}
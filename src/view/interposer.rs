@@ -0,0 +1,123 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+use iced::{Alignment, Element, Length};
+use iced::widget::{button, column, container, row, text, Column};
+use iced_aw::{grid, NumberInput};
+
+use crate::cost::{format_cost, MAXIMUM_COST};
+use crate::interposer::{Interposer, MAXIMUM_CHIPLET_COUNT, MAXIMUM_RETICLE_MULTIPLE};
+use crate::view::calculator::{Component, Message};
+use crate::view::components::labelled_number;
+use crate::view::table::table;
+use crate::view::ROW_HEIGHT;
+use crate::wafer::Wafer;
+
+const INPUT_WIDTH: Length = Length::Fixed(120.0);
+
+pub fn interposer(wafer: &Wafer, interposer: &Interposer) -> Element<'static, Message> {
+	let width_input = labelled_number(
+		"Width (Reticles)",
+		interposer.reticles.0,
+		(0.1, MAXIMUM_RETICLE_MULTIPLE),
+		0.1,
+		Component::InterposerWidth,
+	);
+	let height_input = labelled_number(
+		"Height (Reticles)",
+		interposer.reticles.1,
+		(0.1, MAXIMUM_RETICLE_MULTIPLE),
+		0.1,
+		Component::InterposerHeight,
+	);
+	let yield_input = labelled_number(
+		"Interposer Yield (%)",
+		interposer.interposer_yield,
+		(0.0, 100.0),
+		0.1,
+		Component::InterposerYield,
+	);
+	let assembly_input = labelled_number(
+		"Assembly Yield per Chiplet (%)",
+		interposer.assembly_yield,
+		(0.0, 100.0),
+		0.1,
+		Component::InterposerAssemblyYield,
+	);
+	let inputs = grid![width_input, height_input, yield_input, assembly_input]
+		.column_spacing(16.0)
+		.row_spacing(1.0);
+
+	let header = row![
+		text("Chiplet").width(INPUT_WIDTH),
+		text("Count").width(INPUT_WIDTH),
+		text("Yield (%)").width(INPUT_WIDTH),
+		text("Cost ($)").width(INPUT_WIDTH),
+	]
+	.spacing(8);
+	let mut chiplets = Column::new().spacing(4).push(header);
+	for (i, chiplet) in interposer.chiplets.iter().enumerate() {
+		chiplets = chiplets.push(
+			row![
+				container(text(format!("Chiplet {}", i + 1)))
+					.width(INPUT_WIDTH)
+					.height(ROW_HEIGHT)
+					.center_y(),
+				NumberInput::new(
+					chiplet.count as f32,
+					MAXIMUM_CHIPLET_COUNT as f32,
+					Message::number_input(Component::ChipletCount(i)),
+				)
+				.min(1.0)
+				.step(1.0)
+				.width(INPUT_WIDTH),
+				NumberInput::new(
+					chiplet.die_yield,
+					100.0,
+					Message::number_input(Component::ChipletYield(i)),
+				)
+				.min(0.0)
+				.step(0.1)
+				.width(INPUT_WIDTH),
+				NumberInput::new(
+					chiplet.cost,
+					MAXIMUM_COST,
+					Message::number_input(Component::ChipletCost(i))
+				)
+				.min(0.0)
+				.step(10.0)
+				.width(INPUT_WIDTH),
+				button(text("Remove")).on_press(Message::RemoveChiplet(i)),
+			]
+			.spacing(8)
+			.align_items(Alignment::Center),
+		);
+	}
+	let chiplets = chiplets.push(button(text("Add Chiplet")).on_press(Message::AddChiplet));
+
+	let result = interposer.evaluate(wafer);
+	let results = table(
+		&[
+			"Size (mm)",
+			"Interposers / Wafer",
+			"Good / Wafer",
+			"Interposer Cost",
+			"Package Yield",
+			"Package Cost",
+		],
+		vec![vec![
+			format!("{:.1} × {:.1}", result.width, result.height),
+			result.per_wafer.to_string(),
+			format!("{:.1}", result.good_per_wafer),
+			format_cost(result.interposer_cost),
+			format!("{:.2}%", result.package_yield * 100.0),
+			format_cost(result.package_cost),
+		]],
+	);
+
+	let content = column![inputs, chiplets, results].spacing(16).padding(8);
+	container(content).width(Length::Fill).height(Length::Fill).into()
+}
@@ -9,7 +9,20 @@ use iced::Length;
 pub use calculator::Calculator;
 
 mod calculator;
+mod chart;
+mod compare;
 pub mod components;
+mod fit;
+mod interposer;
+mod models;
+mod partition;
+mod shuttle;
+mod spatial;
+mod stacking;
+mod sweep;
+mod table;
 mod wafer;
+mod waterfall;
+mod zones;
 
 const ROW_HEIGHT: Length = Length::Fixed(48.0);
@@ -0,0 +1,73 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+use iced::{Element, Length};
+use iced::widget::{column, container};
+
+use crate::view::calculator::Message;
+use crate::view::chart::{LineChart, Series, PALETTE};
+use crate::wafer::{Wafer, YieldModel};
+
+const STEPS: usize = 50;
+
+pub fn yield_models(wafer: &Wafer) -> Element<'static, Message> {
+	let area = curves(
+		wafer,
+		String::from("Yield vs Critical Area"),
+		format!("Critical Area (mm²) at D0 = {} /cm²", wafer.defect_rate),
+		(wafer.critical_area * 2.0).max(1.0),
+		wafer.critical_area,
+		|wafer, area| wafer.critical_area = area,
+	);
+	let defect_rate = curves(
+		wafer,
+		String::from("Yield vs Defect Density"),
+		format!("D0 (/cm²) at Critical Area = {} mm²", wafer.critical_area),
+		(wafer.defect_rate * 2.0).max(0.1),
+		wafer.defect_rate,
+		|wafer, defect_rate| wafer.defect_rate = defect_rate,
+	);
+
+	let content = column![area.view(), defect_rate.view()].spacing(16).padding(8);
+	container(content).width(Length::Fill).height(Length::Fill).into()
+}
+
+fn curves(
+	wafer: &Wafer, title: String, x_label: String, maximum: f32, current: f32, set: impl Fn(&mut Wafer, f32),
+) -> LineChart {
+	let mut sample = wafer.clone();
+	let mut model_yield = |model: YieldModel, x: f32| {
+		set(&mut sample, x);
+		model.wafer_yield(&sample) * 100.0
+	};
+
+	let series = YieldModel::ALL
+		.iter()
+		.enumerate()
+		.map(|(i, &model)| Series {
+			label: model.to_string(),
+			color: PALETTE[i % PALETTE.len()],
+			points: (0..=STEPS)
+				.map(|step| {
+					let x = maximum * step as f32 / STEPS as f32;
+					(x, model_yield(model, x))
+				})
+				.collect(),
+		})
+		.collect();
+	let markers = YieldModel::ALL
+		.iter()
+		.map(|&model| (current, model_yield(model, current)))
+		.collect();
+
+	LineChart {
+		title,
+		x_label,
+		y_label: String::from("Yield (%)"),
+		series,
+		markers,
+	}
+}
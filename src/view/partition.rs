@@ -0,0 +1,99 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+use iced::{Element, Length};
+use iced::widget::{column, container, scrollable};
+use iced_aw::grid;
+
+use crate::cost::format_cost;
+use crate::partition::{MAXIMUM_CHIPLETS, Partition};
+use crate::view::calculator::{Component, Message};
+use crate::view::chart::{LineChart, Series, PALETTE};
+use crate::view::components::labelled_number;
+use crate::view::table::table;
+use crate::wafer::Wafer;
+
+pub fn partition(wafer: &Wafer, partition: Partition) -> Element<'static, Message> {
+	let chiplets_input = labelled_number(
+		"Maximum Chiplets",
+		partition.max_chiplets as f32,
+		(1.0, MAXIMUM_CHIPLETS as f32),
+		1.0,
+		Component::Chiplets,
+	);
+	let overhead_input = labelled_number(
+		"Interface Overhead per Chiplet (%)",
+		partition.overhead,
+		(0.0, 100.0),
+		1.0,
+		Component::ChipletOverhead,
+	);
+	let assembly_input = labelled_number(
+		"Assembly Yield per Chiplet (%)",
+		partition.assembly_yield,
+		(0.0, 100.0),
+		0.1,
+		Component::AssemblyYield,
+	);
+	let inputs = grid![chiplets_input, overhead_input, assembly_input]
+		.column_spacing(16.0)
+		.row_spacing(1.0);
+
+	let candidates = partition.candidates(wafer);
+	let rows = candidates
+		.iter()
+		.map(|candidate| {
+			vec![
+				if candidate.chiplets == 1 {
+					String::from("Monolithic")
+				} else {
+					candidate.chiplets.to_string()
+				},
+				format!("{:.2} × {:.2}", candidate.width, candidate.height),
+				candidate.dies_per_wafer.to_string(),
+				format!("{:.2}%", candidate.die_yield * 100.0),
+				format_cost(candidate.cost_per_good_die),
+				format!("{:.2}%", candidate.package_yield * 100.0),
+				format_cost(candidate.cost_per_package),
+			]
+		})
+		.collect();
+	let results = table(
+		&[
+			"Chiplets",
+			"Size (mm)",
+			"Dies / Wafer",
+			"Die Yield",
+			"Cost / Good Die",
+			"Assembly Yield",
+			"Cost / Package",
+		],
+		rows,
+	);
+
+	let chart = LineChart {
+		title: String::from("Cost per Package"),
+		x_label: String::from("Chiplets"),
+		y_label: String::from("Cost ($)"),
+		series: vec![Series {
+			label: String::from("Cost / Package"),
+			color: PALETTE[0],
+			points: candidates
+				.iter()
+				.map(|candidate| (candidate.chiplets as f32, candidate.cost_per_package))
+				.collect(),
+		}],
+		markers: candidates
+			.first()
+			.map(|monolithic| vec![(1.0, monolithic.cost_per_package)])
+			.unwrap_or_default(),
+	};
+
+	let content = column![inputs, scrollable(results).height(Length::Fixed(240.0)), chart.view()]
+		.spacing(16)
+		.padding(8);
+	container(content).width(Length::Fill).height(Length::Fill).into()
+}
@@ -0,0 +1,153 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+use iced::{Alignment, Color, Element, Length, Rectangle, Renderer, Size, Theme, Vector};
+use iced::mouse::Cursor;
+use iced::widget::{button, column, container, row, scrollable, text, text_input, Column};
+use iced::widget::canvas::{Canvas, Frame, Geometry, Program};
+use iced_aw::NumberInput;
+
+use crate::cost::format_cost;
+use crate::die::DieType;
+use crate::render::draw_outline;
+use crate::view::calculator::{Component, Message};
+use crate::view::chart::PALETTE;
+use crate::view::table::table;
+use crate::wafer::{MAXIMUM_DESIGN_COPIES, Shuttle, ShuttleSite, Wafer};
+
+const INPUT_WIDTH: Length = Length::Fixed(100.0);
+
+pub fn shuttle(wafer: &Wafer, shuttle: &Shuttle) -> Element<'static, Message> {
+	let header = row![
+		text("Design").width(Length::Fixed(160.0)),
+		text("Width (mm)").width(INPUT_WIDTH),
+		text("Height (mm)").width(INPUT_WIDTH),
+		text("Copies / Field").width(INPUT_WIDTH),
+	]
+	.spacing(8);
+	let mut designs = Column::new().spacing(4).push(header);
+	for (i, design) in shuttle.designs.iter().enumerate() {
+		designs = designs.push(
+			row![
+				text_input("Name", &design.name)
+					.on_input(move |name| Message::DesignName(i, name))
+					.width(Length::Fixed(160.0)),
+				NumberInput::new(
+					design.width,
+					wafer.reticle.long(),
+					Message::number_input(Component::DesignWidth(i)),
+				)
+				.min(0.1)
+				.step(0.2)
+				.width(INPUT_WIDTH),
+				NumberInput::new(
+					design.height,
+					wafer.reticle.long(),
+					Message::number_input(Component::DesignHeight(i)),
+				)
+				.min(0.1)
+				.step(0.2)
+				.width(INPUT_WIDTH),
+				NumberInput::new(
+					design.copies as f32,
+					MAXIMUM_DESIGN_COPIES as f32,
+					Message::number_input(Component::DesignCopies(i)),
+				)
+				.min(0.0)
+				.step(1.0)
+				.width(INPUT_WIDTH),
+				button(text("Remove")).on_press(Message::RemoveDesign(i)),
+			]
+			.spacing(8)
+			.align_items(Alignment::Center),
+		);
+	}
+	let designs = designs.push(button(text("Add Design")).on_press(Message::AddDesign));
+
+	let placements = shuttle.layout(wafer.reticle.dimensions(), wafer.scribe_lanes);
+	let sites = wafer.shuttle_sites(&placements, shuttle);
+	let results = shuttle.results(wafer, &sites, &placements);
+	let rows = shuttle
+		.designs
+		.iter()
+		.zip(&results)
+		.map(|(design, result)| {
+			vec![
+				design.name.clone(),
+				format!("{} / {}", result.placed, design.copies),
+				result.complete.to_string(),
+				format!("{:.2}%", result.die_yield * 100.0),
+				format!("{:.0}", result.good),
+				format!("{:.1}%", result.area_share * 100.0),
+				format_cost(result.cost_per_good_die),
+			]
+		})
+		.collect();
+	let results = table(
+		&[
+			"Design",
+			"Placed / Field",
+			"Complete Dies",
+			"Die Yield",
+			"Good Dies",
+			"Area Share",
+			"Cost / Good Die",
+		],
+		rows,
+	);
+
+	let canvas = Canvas::new(ShuttleView {
+		wafer: wafer.clone(),
+		sizes: shuttle
+			.designs
+			.iter()
+			.map(|design| (design.width, design.height))
+			.collect(),
+		sites,
+	})
+	.width(Length::Fill)
+	.height(Length::Fill);
+
+	let content = column![designs, scrollable(results).height(Length::Fixed(160.0)), canvas]
+		.spacing(16)
+		.padding(8);
+	container(content).width(Length::Fill).height(Length::Fill).into()
+}
+
+struct ShuttleView {
+	wafer: Wafer,
+	sizes: Vec<(f32, f32)>,
+	sites: Vec<ShuttleSite>,
+}
+
+impl Program<Message> for ShuttleView {
+	type State = ();
+
+	fn draw(
+		&self, _state: &(), renderer: &Renderer, _theme: &Theme, bounds: Rectangle, _cursor: Cursor,
+	) -> Vec<Geometry> {
+		let mut frame = Frame::new(renderer, bounds.size());
+		let dimension = frame.width().min(frame.height()) * 0.9;
+		let center = frame.center();
+		let (scale, top_left) = draw_outline(&mut frame, &self.wafer, center, dimension);
+
+		for site in &self.sites {
+			let (width, height) = self.sizes[site.design];
+			let color = PALETTE[site.design % PALETTE.len()];
+			let alpha = match site.die_type {
+				DieType::Complete => 0.85,
+				_ => 0.3,
+			};
+			frame.fill_rectangle(
+				top_left + Vector::new(site.coord.x, site.coord.y) * scale,
+				Size::new(width * scale, height * scale),
+				Color { a: alpha, ..color },
+			);
+		}
+
+		vec![frame.into_geometry()]
+	}
+}
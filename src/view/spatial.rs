@@ -0,0 +1,73 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+use iced::{Element, Length};
+use iced::widget::{column, container, text};
+
+use crate::analysis::{spatial_statistics, RegionYield};
+use crate::view::calculator::Message;
+use crate::view::table::table;
+use crate::wafer::{Wafer, WaferMap};
+
+pub fn spatial(wafer: &Wafer, map: &WaferMap) -> Element<'static, Message> {
+	let statistics = spatial_statistics(wafer, map);
+	let source = if map.measured.is_empty() {
+		"Simulated Map"
+	} else {
+		"Measured Map"
+	};
+
+	let join_count = statistics.join_count;
+	let summary = table(
+		&["Statistic", "Value"],
+		vec![
+			vec![
+				String::from("Cluster Index"),
+				format!("{:.3}", statistics.cluster_index),
+			],
+			vec![String::from("Bad Clusters"), statistics.clusters.to_string()],
+			vec![String::from("Largest Cluster"), statistics.largest_cluster.to_string()],
+			vec![String::from("Longest Run"), statistics.longest_run.to_string()],
+			vec![String::from("Adjacent Pairs"), join_count.joins.to_string()],
+			vec![String::from("Bad-Bad Joins"), join_count.observed.to_string()],
+			vec![String::from("Expected Joins"), format!("{:.1}", join_count.expected)],
+			vec![String::from("Join Count Z"), format!("{:.2}", join_count.z_score)],
+		],
+	);
+
+	let region = |name: &str, region: RegionYield| {
+		vec![
+			String::from(name),
+			region.tested.to_string(),
+			region.good.to_string(),
+			format!("{:.2}%", region.die_yield() * 100.0),
+		]
+	};
+	let [first, second, third, fourth] = statistics.quadrants;
+	let regions = table(
+		&["Region", "Tested", "Good", "Yield"],
+		vec![
+			region("Whole Wafer", statistics.overall),
+			region("Center", statistics.center),
+			region("Edge", statistics.edge),
+			region("Upper Right", first),
+			region("Upper Left", second),
+			region("Lower Left", third),
+			region("Lower Right", fourth),
+		],
+	);
+
+	let verdict = if join_count.is_clustered() {
+		"Bad dies are clustered; the Poisson model will underestimate yield."
+	} else {
+		"Bad dies are consistent with random placement; the Poisson model is defensible."
+	};
+
+	let content = column![text(source), summary, regions, text(verdict)]
+		.spacing(16)
+		.padding(8);
+	container(content).width(Length::Fill).height(Length::Fill).into()
+}
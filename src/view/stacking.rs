@@ -0,0 +1,77 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+use iced::{Element, Length};
+use iced::widget::{column, container};
+use iced_aw::grid;
+
+use crate::die::DieType;
+use crate::stacking::{MAXIMUM_TIERS, Stack};
+use crate::view::calculator::{Component, Message};
+use crate::view::components::labelled_number;
+use crate::view::table::table;
+use crate::wafer::{Wafer, WaferMap};
+
+pub fn stacking(wafer: &Wafer, map: &WaferMap, stack: Stack, simulated: usize) -> Element<'static, Message> {
+	let tiers_input = labelled_number(
+		"Dies per Stack",
+		stack.tiers as f32,
+		(1.0, MAXIMUM_TIERS as f32),
+		1.0,
+		Component::Tiers,
+	);
+	let coverage_input = labelled_number(
+		"Pre-bond Test Coverage (%)",
+		stack.test_coverage,
+		(0.0, 100.0),
+		1.0,
+		Component::TestCoverage,
+	);
+	let bond_input = labelled_number(
+		"Bond Yield per Step (%)",
+		stack.bond_yield,
+		(0.0, 100.0),
+		0.1,
+		Component::BondYield,
+	);
+	let inputs = grid![tiers_input, coverage_input, bond_input]
+		.column_spacing(16.0)
+		.row_spacing(1.0);
+
+	let sites = map.count(DieType::Complete);
+	let die_yield = wafer.die_yield();
+	let die_to_wafer = stack.die_to_wafer(sites, die_yield);
+	let wafer_to_wafer = stack.wafer_to_wafer(sites, die_yield);
+
+	let results = table(
+		&[
+			"Bonding",
+			"Stacks / Wafer",
+			"Stack Yield",
+			"Good Stacks",
+			"Simulated Good",
+		],
+		vec![
+			vec![
+				String::from("Die-to-Wafer"),
+				format!("{:.0}", die_to_wafer.stacks),
+				format!("{:.2}%", die_to_wafer.stack_yield * 100.0),
+				format!("{:.0}", die_to_wafer.good()),
+				String::from("-"),
+			],
+			vec![
+				String::from("Wafer-to-Wafer"),
+				format!("{:.0}", wafer_to_wafer.stacks),
+				format!("{:.2}%", wafer_to_wafer.stack_yield * 100.0),
+				format!("{:.0}", wafer_to_wafer.good()),
+				simulated.to_string(),
+			],
+		],
+	);
+
+	let content = column![inputs, results].spacing(16).padding(8);
+	container(content).width(Length::Fill).height(Length::Fill).into()
+}
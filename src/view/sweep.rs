@@ -0,0 +1,93 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+use iced::{Alignment, Element, Length};
+use iced::widget::{button, column, container, pick_list, row, text, Row};
+use iced_aw::NumberInput;
+
+use crate::analysis::{Sweep, SweepParameters, SweepRange, SweepResult, MAXIMUM_SWEEP_STEPS, MINIMUM_SWEEP_STEPS};
+use crate::view::calculator::{Component, Message};
+use crate::view::chart::Heatmap;
+use crate::wafer::Wafer;
+
+pub fn sweep(wafer: &Wafer, sweep: Sweep, result: Option<&SweepResult>, running: bool) -> Element<'static, Message> {
+	let mut run = button(text(if running { "Running…" } else { "Run Sweep" }));
+	if !running {
+		run = run.on_press(Message::RunSweep);
+	}
+	let mut export = button(text("Export CSV"));
+	if result.is_some() {
+		export = export.on_press(Message::ExportSweep);
+	}
+	let controls = row![
+		text("Parameters"),
+		pick_list(SweepParameters::ALL, Some(sweep.parameters), Message::SweepParameters),
+		run,
+		export,
+	]
+	.spacing(8)
+	.align_items(Alignment::Center);
+
+	let (x_label, y_label) = sweep.parameters.labels();
+	let (x_maximum, y_maximum) = sweep.parameters.maximum(wafer);
+	let x_inputs = range_inputs(
+		x_label,
+		sweep.x,
+		x_maximum,
+		(Component::SweepXStart, Component::SweepXEnd, Component::SweepXSteps),
+	);
+	let y_inputs = range_inputs(
+		y_label,
+		sweep.y,
+		y_maximum,
+		(Component::SweepYStart, Component::SweepYEnd, Component::SweepYSteps),
+	);
+
+	let mut content = column![controls, x_inputs, y_inputs].spacing(16).padding(8);
+	if let Some(result) = result {
+		let (x_label, y_label) = result.parameters.labels();
+		if let Some((x, y, good)) = result.best() {
+			content = content.push(text(format!(
+				"Most Good Dies: {:.1} at {} = {:.2}, {} = {:.2}",
+				good, x_label, x, y_label, y
+			)));
+		}
+		let heatmap = Heatmap {
+			title: String::from("Good Dies per Wafer"),
+			x_label: String::from(x_label),
+			y_label: String::from(y_label),
+			x: result.x.clone(),
+			y: result.y.clone(),
+			values: result.good.clone(),
+			marker: Some(result.parameters.current(wafer)),
+		};
+		content = content.push(heatmap.view());
+	}
+	container(content).width(Length::Fill).height(Length::Fill).into()
+}
+
+fn range_inputs(
+	label: &str, range: SweepRange, maximum: f32, components: (Component, Component, Component),
+) -> Row<'static, Message> {
+	let step = maximum / 100.0;
+	row![
+		text(label).width(Length::Fixed(160.0)),
+		text("From"),
+		NumberInput::new(range.start, maximum, Message::number_input(components.0)).step(step),
+		text("To"),
+		NumberInput::new(range.end, maximum, Message::number_input(components.1)).step(step),
+		text("Steps"),
+		NumberInput::new(
+			range.steps as f32,
+			MAXIMUM_SWEEP_STEPS as f32,
+			Message::number_input(components.2)
+		)
+		.min(MINIMUM_SWEEP_STEPS as f32)
+		.step(1.0),
+	]
+	.spacing(8)
+	.align_items(Alignment::Center)
+}
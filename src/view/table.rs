@@ -0,0 +1,36 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+use iced::{Element, Font, Length};
+use iced::font::Weight;
+use iced::widget::{text, Column, Row};
+
+use crate::view::calculator::Message;
+
+const CELL_WIDTH: Length = Length::Fixed(120.0);
+
+pub fn table(headers: &[&str], rows: Vec<Vec<String>>) -> Element<'static, Message> {
+	let bold = Font {
+		weight: Weight::Bold,
+		..Font::default()
+	};
+	let header = Row::with_children(
+		headers
+			.iter()
+			.map(|header| text(*header).font(bold).width(CELL_WIDTH).into())
+			.collect(),
+	);
+
+	let mut table = Column::new().spacing(4).push(header);
+	for row in rows {
+		table = table.push(Row::with_children(
+			row.into_iter()
+				.map(|cell| text(cell).width(CELL_WIDTH).into())
+				.collect(),
+		));
+	}
+	table.into()
+}
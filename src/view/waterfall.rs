@@ -0,0 +1,43 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+use iced::{Element, Length};
+use iced::widget::{column, container};
+
+use crate::analysis::AreaStep;
+use crate::view::calculator::Message;
+use crate::view::chart::WaterfallChart;
+use crate::view::table::table;
+
+const SQUARE_CENTIMETRES: f32 = 100.0;
+
+pub fn waterfall(steps: &[AreaStep]) -> Element<'static, Message> {
+	let substrate = steps.first().map_or(1.0, |step| step.area);
+
+	let rows = steps
+		.iter()
+		.map(|step| {
+			vec![
+				String::from(step.label),
+				format!("{:.2}", step.area / SQUARE_CENTIMETRES),
+				format!("{:.2}%", step.area / substrate * 100.0),
+			]
+		})
+		.collect();
+	let results = table(&["Step", "Area (cm²)", "Share"], rows);
+
+	let chart = WaterfallChart {
+		title: String::from("Substrate Area to Good Die Area"),
+		y_label: String::from("Area (cm²)"),
+		steps: steps
+			.iter()
+			.map(|step| (String::from(step.label), step.area / SQUARE_CENTIMETRES, step.total))
+			.collect(),
+	};
+
+	let content = column![results, chart.view()].spacing(16).padding(8);
+	container(content).width(Length::Fill).height(Length::Fill).into()
+}
@@ -0,0 +1,95 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+use iced::{Alignment, Color, Element, Length};
+use iced::widget::{column, container, pick_list, row, text};
+use iced_aw::NumberInput;
+
+use crate::analysis::{radial_zones, Zone, Zones, ZonesOption, MINIMUM_RING_WIDTH};
+use crate::view::calculator::{Component, Message};
+use crate::view::chart::{BarChart, Series, PALETTE};
+use crate::view::table::table;
+use crate::wafer::{Wafer, WaferMap};
+
+pub fn zones(wafer: &Wafer, map: &WaferMap, zones: Zones) -> Element<'static, Message> {
+	let mut controls = row![
+		text("Zones"),
+		pick_list(ZonesOption::ALL, Some(zones.into()), Message::ZonesOption),
+	]
+	.spacing(8)
+	.align_items(Alignment::Center);
+	if let Zones::Rings(width) = zones {
+		controls = controls.push(text("Ring Width (mm)")).push(
+			NumberInput::new(
+				width,
+				wafer.shape.max_width() / 2.0,
+				Message::number_input(Component::RingWidth),
+			)
+			.min(MINIMUM_RING_WIDTH)
+			.step(1.0),
+		);
+	}
+
+	let zones = radial_zones(wafer, map, zones);
+	let measured = !map.measured.is_empty();
+	let rows = zones
+		.iter()
+		.map(|zone| {
+			vec![
+				zone.name.clone(),
+				format!("{:.1} mm", zone.outer),
+				zone.complete.to_string(),
+				percentage(zone.expected_yield()),
+				percentage(zone.simulated_yield()),
+				percentage(zone.measured_yield()),
+			]
+		})
+		.collect();
+	let results = table(
+		&[
+			"Zone",
+			"Outer Radius",
+			"Complete Dies",
+			"Expected Yield",
+			"Simulated Yield",
+			"Measured Yield",
+		],
+		rows,
+	);
+
+	let mut series = vec![
+		bars("Expected", PALETTE[0], &zones, Zone::expected_yield),
+		bars("Simulated", PALETTE[1], &zones, Zone::simulated_yield),
+	];
+	if measured {
+		series.push(bars("Measured", PALETTE[2], &zones, Zone::measured_yield));
+	}
+	let chart = BarChart {
+		title: String::from("Yield by Radial Zone"),
+		y_label: String::from("Yield (%)"),
+		categories: zones.iter().map(|zone| zone.name.clone()).collect(),
+		series,
+	};
+
+	let content = column![controls, results, chart.view()].spacing(16).padding(8);
+	container(content).width(Length::Fill).height(Length::Fill).into()
+}
+
+fn percentage(value: Option<f32>) -> String {
+	value.map_or_else(|| String::from("–"), |value| format!("{:.2}%", value * 100.0))
+}
+
+fn bars(label: &str, color: Color, zones: &[Zone], value: impl Fn(&Zone) -> Option<f32>) -> Series {
+	Series {
+		label: String::from(label),
+		color,
+		points: zones
+			.iter()
+			.enumerate()
+			.filter_map(|(i, zone)| value(zone).map(|value| (i as f32, value * 100.0)))
+			.collect(),
+	}
+}
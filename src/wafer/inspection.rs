@@ -0,0 +1,110 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+use std::collections::BTreeMap;
+
+use crate::die::DieType;
+use crate::util::Coordinate;
+use crate::wafer::{Wafer, WaferMap};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Defect {
+	pub x: f32,
+	pub y: f32,
+	pub size: f32,
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct LocatedDefect {
+	pub coord: Coordinate,
+	pub size: f32,
+	pub site: Option<(usize, usize)>,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct Inspection {
+	pub defects: Vec<LocatedDefect>,
+	pub hits: BTreeMap<(usize, usize), usize>,
+	pub killed: f32,
+	geometry: [f32; 4],
+}
+
+impl Inspection {
+	pub fn forecast_good(&self, map: &WaferMap) -> f32 {
+		map.count(DieType::Complete) as f32 - self.killed
+	}
+
+	pub fn kill(&mut self, kill_ratio: f32) {
+		self.killed = self
+			.hits
+			.values()
+			.map(|&count| 1.0 - (1.0 - kill_ratio).powi(count as i32))
+			.sum();
+	}
+
+	pub fn is_current(&self, wafer: &Wafer, previous: &WaferMap, map: &WaferMap) -> bool {
+		self.geometry == geometry(wafer) && previous.same_layout(map)
+	}
+}
+
+fn geometry(wafer: &Wafer) -> [f32; 4] {
+	[
+		wafer.shape.max_width(),
+		wafer.shape.max_height(),
+		wafer.die.width(),
+		wafer.die.height(),
+	]
+}
+
+fn locate(map: &WaferMap, coord: Coordinate, width: f32, height: f32) -> Option<(usize, usize)> {
+	let rows = map.sites.first()?;
+	let y = rows.partition_point(|site| site.coord.y + height <= coord.y);
+	if rows.get(y)?.coord.y > coord.y {
+		return None;
+	}
+	let x = map.sites.partition_point(|column| column[y].coord.x + width <= coord.x);
+	let site = map.sites.get(x)?[y];
+	(site.die_type == DieType::Complete && site.coord.x <= coord.x).then_some((x, y))
+}
+
+impl Wafer {
+	pub fn inspect(&self, map: &WaferMap, defects: &[Defect], kill_ratio: f32) -> Inspection {
+		let geometry = geometry(self);
+		let center = Coordinate {
+			x: geometry[0] / 2.0,
+			y: geometry[1] / 2.0,
+		};
+
+		let mut hits = BTreeMap::new();
+		let defects = defects
+			.iter()
+			.map(|defect| {
+				let coord = Coordinate {
+					x: center.x + defect.x,
+					y: center.y - defect.y,
+				};
+				let site = locate(map, coord, geometry[2], geometry[3]);
+				if let Some(site) = site {
+					*hits.entry(site).or_insert(0) += 1;
+				}
+				LocatedDefect {
+					coord,
+					size: defect.size,
+					site,
+				}
+			})
+			.collect();
+
+		let mut inspection = Inspection {
+			defects,
+			hits,
+			killed: 0.0,
+			geometry,
+		};
+		inspection.kill(kill_ratio);
+		inspection
+	}
+}
@@ -0,0 +1,142 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+use crate::die::DieType;
+use crate::util::{sample, Coordinate};
+use crate::wafer::{Inspection, Wafer};
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Fault {
+	Random,
+	Reticle,
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct Site {
+	pub die_type: DieType,
+	pub coord: Coordinate,
+	pub fault: Option<Fault>,
+}
+
+impl Site {
+	pub fn is_good(&self) -> bool {
+		self.die_type == DieType::Complete && self.fault.is_none()
+	}
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Measurement {
+	pub column: usize,
+	pub row: usize,
+	pub bin: u32,
+	pub pass: bool,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct WaferMap {
+	pub sites: Vec<Vec<Site>>,
+	pub secondary: Vec<Site>,
+	pub measured: Vec<Measurement>,
+	pub inspection: Option<Inspection>,
+}
+
+impl WaferMap {
+	pub fn simulate(wafer: &Wafer) -> WaferMap {
+		let dies = wafer.get_dies();
+		let mut secondary: Vec<Site> = wafer
+			.secondary_dies(&dies)
+			.into_iter()
+			.map(|coord| Site {
+				die_type: DieType::Complete,
+				coord,
+				fault: None,
+			})
+			.collect();
+		let mut sites: Vec<Vec<Site>> = dies
+			.into_iter()
+			.enumerate()
+			.map(|(x, column)| {
+				column
+					.into_iter()
+					.enumerate()
+					.map(|(y, (die_type, coord))| {
+						let reticle_defect = die_type == DieType::Complete
+							&& wafer.reticle_defects.contains(&wafer.field_position(x, y));
+						Site {
+							die_type,
+							coord,
+							fault: reticle_defect.then_some(Fault::Reticle),
+						}
+					})
+					.collect()
+			})
+			.collect();
+
+		let candidates: Vec<(usize, usize)> = sites
+			.iter()
+			.enumerate()
+			.flat_map(|(x, column)| column.iter().enumerate().map(move |(y, site)| (x, y, site)))
+			.filter(|(_, _, site)| site.is_good())
+			.map(|(x, y, _)| (x, y))
+			.collect();
+
+		let die_yield = wafer.die_yield();
+		let bad_dies = ((candidates.len() as f32) * (1.0 - die_yield)).round() as usize;
+		for index in sample(candidates.len(), bad_dies) {
+			let (x, y) = candidates[index];
+			sites[x][y].fault = Some(Fault::Random);
+		}
+
+		let secondary_yield = wafer.secondary_yield();
+		let bad_secondary = ((secondary.len() as f32) * (1.0 - secondary_yield)).round() as usize;
+		for index in sample(secondary.len(), bad_secondary) {
+			secondary[index].fault = Some(Fault::Random);
+		}
+
+		WaferMap {
+			sites,
+			secondary,
+			measured: Vec::new(),
+			inspection: None,
+		}
+	}
+
+	pub fn sites(&self) -> impl Iterator<Item = &Site> {
+		self.sites.iter().flatten()
+	}
+
+	pub fn count(&self, die_type: DieType) -> usize {
+		self.sites().filter(|site| site.die_type == die_type).count()
+	}
+
+	pub fn same_layout(&self, other: &WaferMap) -> bool {
+		self.sites.len() == other.sites.len()
+			&& self.sites.iter().zip(&other.sites).all(|(column, other)| {
+				column.len() == other.len()
+					&& column.iter().zip(other).all(|(site, other)| {
+						site.die_type == other.die_type
+							&& site.coord.x == other.coord.x
+							&& site.coord.y == other.coord.y
+					})
+			})
+	}
+
+	pub fn faults(&self, fault: Fault) -> usize {
+		self.sites().filter(|site| site.fault == Some(fault)).count()
+	}
+
+	pub fn good(&self) -> usize {
+		self.sites().filter(|site| site.is_good()).count()
+	}
+
+	pub fn secondary_good(&self) -> usize {
+		self.secondary.iter().filter(|site| site.is_good()).count()
+	}
+
+	pub fn measured_good(&self) -> usize {
+		self.measured.iter().filter(|measurement| measurement.pass).count()
+	}
+}
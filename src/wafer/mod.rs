@@ -4,47 +4,233 @@
  * file, You can obtain one at http://mozilla.org/MPL/2.0/.
  */
 
+pub use inspection::*;
+pub use map::*;
+pub use reticle::*;
+pub use secondary::*;
 pub use shape::*;
+pub use shuttle::*;
+pub use stagger::*;
+pub use stitching::*;
 pub use yield_model::YieldModel;
 
-use crate::die::{Die, DieType};
+use std::collections::BTreeSet;
+
+use serde::{Deserialize, Serialize};
+
+use crate::cost::{Cost, MAXIMUM_COST};
+use crate::die::{Die, DieType, MAXIMUM_DIE_BORDER};
 use crate::util::{Coordinate, Rectangle};
 
+mod inspection;
+mod map;
+mod reticle;
+mod secondary;
 mod shape;
+mod shuttle;
+mod stagger;
+mod stitching;
 mod yield_model;
 
 pub const MAXIMUM_SCRIBE_WIDTH: f32 = 10.0;
 pub const MINIMUM_DIE_DIMENSION: f32 = 0.01;
+pub const MAXIMUM_CLUSTER_PARAMETER: f32 = 50.0;
+pub const MINIMUM_CLUSTER_PARAMETER: f32 = 0.05;
+pub const MAXIMUM_EDGE_LOSS: f32 = 25.0;
+pub const MAXIMUM_DEFECT_RATE: f32 = 10000.0;
 
-#[derive(Clone, Copy, Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+#[serde(default)]
 pub struct Wafer {
 	pub critical_area: f32,
 	pub shape: Shape,
+	pub reticle: Reticle,
 	pub edge_loss: f32,
 	pub defect_rate: f32,
 
 	pub scribe_lanes: (f32, f32),
+	pub field_scribe_lanes: (f32, f32),
 	pub translation: (f32, f32),
 	pub centered: bool,
+	pub stagger: Stagger,
 
 	pub die: Die,
+	pub stitching: Stitching,
+	pub secondary: Secondary,
+	pub reticle_defects: BTreeSet<(u32, u32)>,
 	pub yield_model: YieldModel,
+	pub cluster: f32,
+	pub cost: Cost,
 }
 
 impl Wafer {
-	fn reticle(&self) -> Die {
-		Die::Rectangle {
-			width: self.die.width() + self.scribe_lanes.0,
-			height: self.die.height() + self.scribe_lanes.1,
+	pub fn exposures(&self) -> Exposures {
+		Exposures::new(self.die, self.reticle)
+	}
+
+	pub fn field(&self) -> (f32, f32) {
+		let (width, height) = self.reticle.dimensions();
+		let count = |field_width: f32, field_height: f32| {
+			fit(
+				field_width,
+				self.die.width(),
+				self.scribe_lanes.0,
+				self.field_scribe_lanes.0,
+			) * fit(
+				field_height,
+				self.die.height(),
+				self.scribe_lanes.1,
+				self.field_scribe_lanes.1,
+			)
+		};
+
+		if count(height, width) > count(width, height) {
+			(height, width)
+		} else {
+			(width, height)
+		}
+	}
+
+	pub fn dies_per_field(&self) -> (u32, u32) {
+		if self.stitching.enabled && self.exposures().is_stitched() {
+			return (1, 1);
+		}
+
+		let (width, height) = self.field();
+		(
+			fit(width, self.die.width(), self.scribe_lanes.0, self.field_scribe_lanes.0),
+			fit(
+				height,
+				self.die.height(),
+				self.scribe_lanes.1,
+				self.field_scribe_lanes.1,
+			),
+		)
+	}
+
+	fn axes(&self) -> (Axis, Axis) {
+		let (columns, rows) = self.dies_per_field();
+		(
+			Axis {
+				length: self.shape.max_width(),
+				die: self.die.width(),
+				scribe: self.scribe_lanes.0,
+				field_scribe: self.field_scribe_lanes.0,
+				per_field: columns,
+				translation: self.translation.0,
+			},
+			Axis {
+				length: self.shape.max_height(),
+				die: self.die.height(),
+				scribe: self.scribe_lanes.1,
+				field_scribe: self.field_scribe_lanes.1,
+				per_field: rows,
+				translation: self.translation.1,
+			},
+		)
+	}
+
+	pub fn field_position(&self, column: usize, row: usize) -> (u32, u32) {
+		let (horizontal, vertical) = self.axes();
+		(horizontal.field_position(column), vertical.field_position(row))
+	}
+
+	pub fn site_index(&self, column: usize, row: usize) -> (i32, i32) {
+		let (horizontal, vertical) = self.axes();
+		(horizontal.index(column), vertical.index(row))
+	}
+
+	pub fn average_pitch(&self) -> (f32, f32) {
+		let (horizontal, vertical) = self.axes();
+		(horizontal.average_pitch(), vertical.average_pitch())
+	}
+
+	pub fn clamp(&mut self, reticle_limit: bool) {
+		self.edge_loss = self.edge_loss.clamp(0.0, MAXIMUM_EDGE_LOSS);
+		self.defect_rate = self.defect_rate.clamp(0.0, MAXIMUM_DEFECT_RATE);
+		self.cluster = self.cluster.clamp(MINIMUM_CLUSTER_PARAMETER, MAXIMUM_CLUSTER_PARAMETER);
+
+		let scribe = |(x, y): (f32, f32)| (x.clamp(0.0, MAXIMUM_SCRIBE_WIDTH), y.clamp(0.0, MAXIMUM_SCRIBE_WIDTH));
+		self.scribe_lanes = scribe(self.scribe_lanes);
+		self.field_scribe_lanes = scribe(self.field_scribe_lanes);
+
+		if let Reticle::Custom { width, height } = &mut self.reticle {
+			*width = width.clamp(MINIMUM_DIE_DIMENSION, MAXIMUM_RETICLE_DIMENSION);
+			*height = height.clamp(MINIMUM_DIE_DIMENSION, MAXIMUM_RETICLE_DIMENSION);
+		}
+
+		self.die.seal_ring = self.die.seal_ring.clamp(0.0, MAXIMUM_DIE_BORDER);
+		self.die.keep_out = self.die.keep_out.clamp(0.0, MAXIMUM_DIE_BORDER);
+		let (_, width) = self.die.width_bounds(reticle_limit, self.reticle, self.shape);
+		self.die = self.die.new_width(self.die.active_width().min(width));
+		let (_, height) = self.die.height_bounds(reticle_limit, self.reticle, self.shape);
+		self.die = self.die.new_height(self.die.active_height().min(height));
+		self.translation = (
+			self.translation.0.clamp(0.0, self.die.width()),
+			self.translation.1.clamp(0.0, self.die.height()),
+		);
+
+		match &mut self.stagger {
+			Stagger::None => {}
+			Stagger::Alternating(fraction) | Stagger::Progressive(fraction) => {
+				*fraction = fraction.clamp(0.0, MAXIMUM_STAGGER);
+			}
+			Stagger::PerRow(offsets) => {
+				for offset in offsets {
+					*offset = offset.clamp(0.0, MAXIMUM_STAGGER);
+				}
+			}
+		}
+
+		self.stitching.yield_loss = self.stitching.yield_loss.clamp(0.0, 100.0);
+		self.stitching.keep_out = self.stitching.keep_out.clamp(0.0, MAXIMUM_STITCH_KEEP_OUT);
+		self.secondary.width = self.secondary.width.max(MINIMUM_DIE_DIMENSION);
+		self.secondary.height = self.secondary.height.max(MINIMUM_DIE_DIMENSION);
+		self.cost.wafer = self.cost.wafer.clamp(0.0, MAXIMUM_COST);
+		self.cost.test = self.cost.test.clamp(0.0, MAXIMUM_COST);
+		self.cost.packaging = self.cost.packaging.clamp(0.0, MAXIMUM_COST);
+
+		self.critical_area = self.critical_area.max(0.0);
+		self.clamp_critical_area();
+		self.clamp_reticle_defects();
+	}
+
+	pub fn clamp_reticle_defects(&mut self) {
+		let (columns, rows) = self.dies_per_field();
+		self.reticle_defects.retain(|&(x, y)| x < columns && y < rows);
+	}
+
+	pub fn usable_area(&self) -> f32 {
+		if self.stitching.enabled {
+			(self.die.active_area() - self.exposures().keep_out_area(self.die, self.stitching.keep_out)).max(0.0)
+		} else {
+			self.die.active_area()
 		}
 	}
 
 	pub fn clamp_critical_area(&mut self) {
-		self.critical_area = self.critical_area.min(self.die.area());
+		self.critical_area = self.critical_area.min(self.usable_area());
+	}
+
+	pub fn stitch_yield(&self) -> f32 {
+		if self.stitching.enabled {
+			let stitches = self.exposures().stitches() as i32;
+			(1.0 - self.stitching.yield_loss / 100.0).powi(stitches)
+		} else {
+			1.0
+		}
+	}
+
+	pub fn die_yield(&self) -> f32 {
+		self.yield_model.wafer_yield(self) * self.stitch_yield()
 	}
 
 	fn die_type(&self, die_coord: Coordinate) -> DieType {
-		let die = Rectangle::new(die_coord, self.die.width(), self.die.height());
+		self.site_type(die_coord, self.die.width(), self.die.height())
+	}
+
+	fn site_type(&self, die_coord: Coordinate, width: f32, height: f32) -> DieType {
+		let die = Rectangle::new(die_coord, width, height);
 
 		let (within, within_inner) = match self.shape {
 			Shape::Wafer(diameter) => {
@@ -85,35 +271,23 @@ impl Wafer {
 	}
 
 	pub fn get_dies(&self) -> Vec<Vec<(DieType, Coordinate)>> {
-		let horizontal = (self.shape.max_width() / self.reticle().width()).floor() as u32;
-		let vertical = (self.shape.max_height() / self.reticle().height()).floor() as u32;
+		let (horizontal, vertical) = self.axes();
+		let staggered = self.stagger.is_staggered();
+		let columns = horizontal.count() + staggered as usize;
 
-		let horizontal_even = horizontal % 2 == 0;
-		let vertical_even = vertical % 2 == 0;
-		let reticle = self.reticle();
-
-		(0..=horizontal)
+		(0..=columns)
 			.map(|x| {
-				(0..=vertical)
+				(0..=vertical.count())
 					.map(|y| {
-						let x_offset = if self.centered == horizontal_even {
-							0.5 * self.scribe_lanes.0
+						let shift = if staggered {
+							let field_row = vertical.index(y).div_euclid(vertical.per_field as i32);
+							(self.stagger.shift(field_row) - 1.0) * horizontal.average_pitch()
 						} else {
-							-0.5 * self.die.width()
+							0.0
 						};
-						let y_offset = if self.centered == vertical_even {
-							0.5 * self.scribe_lanes.1
-						} else {
-							-0.5 * self.die.height()
-						};
-
 						let coord = Coordinate {
-							x: self.shape.max_width() / 2.0
-								+ ((x as f32) - 0.5 * (horizontal as f32)).floor() * reticle.width()
-								+ x_offset + self.translation.0,
-							y: self.shape.max_height() / 2.0
-								+ ((y as f32) - 0.5 * (vertical as f32)).floor() * reticle.height()
-								+ y_offset + self.translation.1,
+							x: horizontal.position(x, self.centered) + shift,
+							y: vertical.position(y, self.centered),
 						};
 						(self.die_type(coord), coord)
 					})
@@ -123,20 +297,183 @@ impl Wafer {
 	}
 }
 
+#[derive(Clone, Copy, Debug)]
+struct Axis {
+	length: f32,
+	die: f32,
+	scribe: f32,
+	field_scribe: f32,
+	per_field: u32,
+	translation: f32,
+}
+
+impl Axis {
+	fn pitch(&self) -> f32 {
+		self.die + self.scribe
+	}
+
+	fn field_pitch(&self) -> f32 {
+		let per_field = self.per_field as f32;
+		per_field * self.die + (per_field - 1.0) * self.scribe + self.field_scribe
+	}
+
+	fn average_pitch(&self) -> f32 {
+		self.field_pitch() / self.per_field as f32
+	}
+
+	fn count(&self) -> usize {
+		(self.length * self.per_field as f32 / self.field_pitch()).floor() as usize
+	}
+
+	fn index(&self, i: usize) -> i32 {
+		i as i32 + (-0.5 * self.count() as f32).floor() as i32
+	}
+
+	fn field_position(&self, i: usize) -> u32 {
+		self.index(i).rem_euclid(self.per_field as i32) as u32
+	}
+
+	fn position(&self, i: usize, centered: bool) -> f32 {
+		let index = self.index(i);
+		let per_field = self.per_field as i32;
+		let offset = if centered == (self.count() % 2 == 0) {
+			0.5 * self.field_scribe
+		} else {
+			-0.5 * self.die
+		};
+
+		self.length / 2.0
+			+ index.div_euclid(per_field) as f32 * self.field_pitch()
+			+ index.rem_euclid(per_field) as f32 * self.pitch()
+			+ offset + self.translation
+	}
+}
+
+fn fit(field: f32, die: f32, scribe: f32, field_scribe: f32) -> u32 {
+	((field - field_scribe + scribe) / (die + scribe)).floor().max(1.0) as u32
+}
+
 impl Default for Wafer {
 	fn default() -> Wafer {
 		Wafer {
-			critical_area: Die::default().area(),
+			critical_area: Die::default().active_area(),
 			shape: Shape::default(),
+			reticle: Reticle::default(),
 			edge_loss: 3.0,
 			defect_rate: 0.1,
 
 			scribe_lanes: (0.25, 0.25),
+			field_scribe_lanes: (0.25, 0.25),
 			translation: (0.0, 0.0),
 			centered: false,
+			stagger: Stagger::default(),
 
 			die: Die::default(),
+			stitching: Stitching::default(),
+			secondary: Secondary::default(),
+			reticle_defects: BTreeSet::new(),
 			yield_model: YieldModel::default(),
+			cluster: 2.0,
+			cost: Cost::default(),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn panel(die: f32, scribe: f32, field_scribe: f32) -> Wafer {
+		Wafer {
+			shape: Shape::Panel(Panel::TwelveByTwelve),
+			reticle: Reticle::Custom {
+				width: 20.0,
+				height: 20.0,
+			},
+			edge_loss: 0.0,
+			scribe_lanes: (scribe, scribe),
+			field_scribe_lanes: (field_scribe, field_scribe),
+			die: Die::default().new_width(die).new_height(die),
+			..Wafer::default()
 		}
 	}
+
+	fn complete(wafer: &Wafer) -> usize {
+		wafer
+			.get_dies()
+			.iter()
+			.flatten()
+			.filter(|(die_type, _)| *die_type == DieType::Complete)
+			.count()
+	}
+
+	#[test]
+	fn fits_dies_in_field() {
+		assert_eq!(fit(26.0, 8.0, 0.25, 0.25), 3);
+		assert_eq!(fit(33.0, 8.0, 0.25, 0.25), 4);
+		assert_eq!(fit(26.0, 5.0, 0.1, 1.0), 4);
+		assert_eq!(fit(10.0, 20.0, 0.25, 0.25), 1);
+	}
+
+	#[test]
+	fn counts_dies_per_field() {
+		assert_eq!(Wafer::default().dies_per_field(), (3, 4));
+		assert_eq!(panel(10.0, 0.0, 0.0).dies_per_field(), (2, 2));
+		assert_eq!(panel(10.0, 0.0, 2.0).dies_per_field(), (1, 1));
+		assert_eq!(panel(8.5, 0.5, 2.0).dies_per_field(), (2, 2));
+	}
+
+	#[test]
+	fn lays_out_dies_without_field_scribe() {
+		let mut wafer = panel(10.0, 0.0, 0.0);
+		assert_eq!(complete(&wafer), 29 * 29);
+		wafer.centered = true;
+		assert_eq!(complete(&wafer), 30 * 30);
+	}
+
+	#[test]
+	fn lays_out_dies_with_field_scribe() {
+		let mut wafer = panel(10.0, 0.0, 2.0);
+		assert_eq!(complete(&wafer), 24 * 24);
+		wafer.centered = true;
+		assert_eq!(complete(&wafer), 25 * 25);
+
+		let dies = panel(8.5, 0.5, 2.0).get_dies();
+		let steps: Vec<f32> = dies
+			.windows(2)
+			.take(4)
+			.map(|pair| pair[1][0].1.x - pair[0][0].1.x)
+			.collect();
+		assert!(steps.contains(&9.0) && steps.contains(&10.5), "{:?}", steps);
+		assert!(steps.iter().all(|&step| step == 9.0 || step == 10.5), "{:?}", steps);
+	}
+
+	#[test]
+	fn staggers_alternating_field_rows() {
+		let mut wafer = panel(10.0, 0.0, 0.0);
+		let aligned = wafer.get_dies();
+		wafer.stagger = Stagger::Alternating(0.5);
+		let dies = wafer.get_dies();
+		assert_eq!(dies.len(), aligned.len() + 1);
+
+		let column = &dies[10];
+		assert_eq!(column[15].1.x, aligned[10][15].1.x - 10.0);
+		assert_eq!(column[16].1.x, column[15].1.x);
+		assert_eq!(column[17].1.x - column[15].1.x, 5.0);
+		assert_eq!(column[18].1.x, column[17].1.x);
+		assert_eq!(column[19].1.x, column[15].1.x);
+		assert_eq!(column[15].1.y, aligned[10][15].1.y);
+	}
+
+	#[test]
+	fn staggers_per_row_offsets() {
+		let mut wafer = panel(10.0, 0.0, 2.0);
+		wafer.stagger = Stagger::PerRow(vec![0.0, 0.25, 0.5]);
+		let column = &wafer.get_dies()[10];
+		let base = column[13].1.x;
+		assert_eq!(column[14].1.x - base, 3.0);
+		assert_eq!(column[15].1.x - base, 6.0);
+		assert_eq!(column[16].1.x, base);
+		assert_eq!(column[12].1.x - base, 6.0);
+	}
 }
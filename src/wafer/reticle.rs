@@ -0,0 +1,85 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+use std::fmt;
+use std::fmt::{Display, Formatter};
+
+use serde::{Deserialize, Serialize};
+
+pub const MAXIMUM_RETICLE_DIMENSION: f32 = 50.0;
+
+#[derive(Copy, Clone, Debug, Default, PartialEq, Deserialize, Serialize)]
+pub enum Reticle {
+	#[default]
+	FullField,
+	HighNa,
+	ILine,
+	Custom {
+		width: f32,
+		height: f32,
+	},
+}
+
+impl Reticle {
+	pub fn dimensions(self) -> (f32, f32) {
+		match self {
+			Reticle::FullField => (26.0, 33.0),
+			Reticle::HighNa => (26.0, 16.5),
+			Reticle::ILine => (22.0, 22.0),
+			Reticle::Custom { width, height } => (width, height),
+		}
+	}
+
+	pub fn long(self) -> f32 {
+		let (width, height) = self.dimensions();
+		width.max(height)
+	}
+
+	pub fn short(self) -> f32 {
+		let (width, height) = self.dimensions();
+		width.min(height)
+	}
+}
+
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub enum ReticleOption {
+	#[default]
+	FullField,
+	HighNa,
+	ILine,
+	Custom,
+}
+
+impl ReticleOption {
+	pub const ALL: &'static [ReticleOption] = &[
+		ReticleOption::FullField,
+		ReticleOption::HighNa,
+		ReticleOption::ILine,
+		ReticleOption::Custom,
+	];
+}
+
+impl From<Reticle> for ReticleOption {
+	fn from(reticle: Reticle) -> ReticleOption {
+		match reticle {
+			Reticle::FullField => ReticleOption::FullField,
+			Reticle::HighNa => ReticleOption::HighNa,
+			Reticle::ILine => ReticleOption::ILine,
+			Reticle::Custom { .. } => ReticleOption::Custom,
+		}
+	}
+}
+
+impl Display for ReticleOption {
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		match self {
+			ReticleOption::FullField => f.write_str("Full Field (26 × 33 mm²)"),
+			ReticleOption::HighNa => f.write_str("High-NA Half Field (26 × 16.5 mm²)"),
+			ReticleOption::ILine => f.write_str("i-line Stepper (22 × 22 mm²)"),
+			ReticleOption::Custom => f.write_str("Custom"),
+		}
+	}
+}
@@ -0,0 +1,154 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+use serde::{Deserialize, Serialize};
+
+use crate::die::DieType;
+use crate::util::Coordinate;
+use crate::wafer::{Wafer, MINIMUM_DIE_DIMENSION};
+
+pub const MAXIMUM_SECONDARY_SITES: usize = 500_000;
+
+#[derive(Clone, Copy, Debug, PartialEq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct Secondary {
+	pub enabled: bool,
+	pub width: f32,
+	pub height: f32,
+}
+
+impl Secondary {
+	pub fn area(&self) -> f32 {
+		self.width * self.height
+	}
+}
+
+impl Default for Secondary {
+	fn default() -> Secondary {
+		Secondary {
+			enabled: false,
+			width: 2.0,
+			height: 2.0,
+		}
+	}
+}
+
+impl Wafer {
+	pub fn secondary_yield(&self) -> f32 {
+		let mut secondary = self.clone();
+		secondary.critical_area = self.secondary.area();
+		secondary.yield_model.wafer_yield(&secondary)
+	}
+
+	fn secondary_pitch(&self) -> (f32, f32) {
+		(
+			self.secondary.width.max(MINIMUM_DIE_DIMENSION) + self.scribe_lanes.0,
+			self.secondary.height.max(MINIMUM_DIE_DIMENSION) + self.scribe_lanes.1,
+		)
+	}
+
+	pub fn secondary_sites(&self) -> usize {
+		let (pitch_x, pitch_y) = self.secondary_pitch();
+		let columns = (self.shape.max_width() / pitch_x).ceil() as usize + 1;
+		let rows = (self.shape.max_height() / pitch_y).ceil() as usize + 1;
+		columns.saturating_mul(rows)
+	}
+
+	pub fn secondary_dies(&self, primary: &[Vec<(DieType, Coordinate)>]) -> Vec<Coordinate> {
+		if !self.secondary.enabled || self.secondary_sites() > MAXIMUM_SECONDARY_SITES {
+			return Vec::new();
+		}
+
+		let (width, height) = (
+			self.secondary.width.max(MINIMUM_DIE_DIMENSION),
+			self.secondary.height.max(MINIMUM_DIE_DIMENSION),
+		);
+		let (scribe_x, scribe_y) = self.scribe_lanes;
+		let (pitch_x, pitch_y) = self.secondary_pitch();
+
+		let (die_width, die_height) = (self.die.width(), self.die.height());
+		let rows: Vec<f32> = primary
+			.first()
+			.map(|column| column.iter().map(|(_, coord)| coord.y).collect())
+			.unwrap_or_default();
+		let overlaps = |coord: Coordinate| {
+			let first_row = rows.partition_point(|&y| y + die_height + scribe_y <= coord.y);
+			let last_row = rows.partition_point(|&y| y < coord.y + height + scribe_y);
+			(first_row..last_row).any(|y| {
+				let first_column = primary.partition_point(|column| column[y].1.x + die_width + scribe_x <= coord.x);
+				let last_column = primary.partition_point(|column| column[y].1.x < coord.x + width + scribe_x);
+				primary[first_column..last_column.max(first_column)]
+					.iter()
+					.any(|column| column[y].0 == DieType::Complete)
+			})
+		};
+
+		let origin = primary
+			.first()
+			.and_then(|column| column.first())
+			.map(|(_, coord)| *coord)
+			.unwrap_or(Coordinate { x: 0.0, y: 0.0 });
+		let start = Coordinate {
+			x: origin.x.rem_euclid(pitch_x) - pitch_x,
+			y: origin.y.rem_euclid(pitch_y) - pitch_y,
+		};
+		let columns = ((self.shape.max_width() - start.x) / pitch_x).ceil() as usize;
+		let rows = ((self.shape.max_height() - start.y) / pitch_y).ceil() as usize;
+
+		(0..columns)
+			.flat_map(|x| (0..rows).map(move |y| (x, y)))
+			.map(|(x, y)| Coordinate {
+				x: start.x + x as f32 * pitch_x,
+				y: start.y + y as f32 * pitch_y,
+			})
+			.filter(|coord| self.site_type(*coord, width, height) == DieType::Complete && !overlaps(*coord))
+			.collect()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn wafer(size: f32) -> Wafer {
+		let mut wafer = Wafer::default();
+		wafer.die = wafer.die.new_width(20.0).new_height(20.0);
+		wafer.secondary = Secondary {
+			enabled: true,
+			width: size,
+			height: size,
+		};
+		wafer
+	}
+
+	#[test]
+	fn places_sub_millimetre_dies_clear_of_primary_dies() {
+		let wafer = wafer(0.5);
+		let primary = wafer.get_dies();
+		let secondary = wafer.secondary_dies(&primary);
+		assert!(!secondary.is_empty());
+
+		let (width, height) = (wafer.die.width(), wafer.die.height());
+		let overlapping = |coord: &Coordinate| {
+			primary.iter().flatten().any(|(die_type, die)| {
+				*die_type == DieType::Complete
+					&& coord.x < die.x + width
+					&& die.x < coord.x + 0.5
+					&& coord.y < die.y + height
+					&& die.y < coord.y + 0.5
+			})
+		};
+		assert!(!secondary.iter().any(overlapping));
+	}
+
+	#[test]
+	fn skips_oversized_grids() {
+		let mut wafer = wafer(MINIMUM_DIE_DIMENSION);
+		wafer.scribe_lanes = (0.0, 0.0);
+		assert!(wafer.secondary_sites() > MAXIMUM_SECONDARY_SITES);
+		assert!(wafer.secondary_dies(&wafer.get_dies()).is_empty());
+	}
+}
@@ -7,7 +7,9 @@
 use std::fmt;
 use std::fmt::{Display, Formatter};
 
-#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+use serde::{Deserialize, Serialize};
+
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Deserialize, Serialize)]
 pub enum Diameter {
 	Two,
 	Three,
@@ -61,7 +63,7 @@ impl Display for Diameter {
 	}
 }
 
-#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Deserialize, Serialize)]
 pub enum Panel {
 	#[default]
 	TwelveByTwelve,
@@ -107,7 +109,7 @@ impl Display for Panel {
 	}
 }
 
-#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Deserialize, Serialize)]
 pub enum Shape {
 	Wafer(Diameter),
 	Panel(Panel),
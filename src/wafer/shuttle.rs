@@ -0,0 +1,274 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+use serde::{Deserialize, Serialize};
+
+use crate::die::DieType;
+use crate::util::Coordinate;
+use crate::wafer::Wafer;
+
+pub const MAXIMUM_DESIGN_COPIES: u32 = 64;
+
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct Design {
+	pub name: String,
+	pub width: f32,
+	pub height: f32,
+	pub copies: u32,
+}
+
+impl Design {
+	pub fn area(&self) -> f32 {
+		self.width * self.height
+	}
+}
+
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct Shuttle {
+	pub designs: Vec<Design>,
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct Placement {
+	pub design: usize,
+	pub offset: Coordinate,
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct ShuttleSite {
+	pub design: usize,
+	pub die_type: DieType,
+	pub coord: Coordinate,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct DesignResult {
+	pub placed: u32,
+	pub complete: usize,
+	pub die_yield: f32,
+	pub good: f32,
+	pub area_share: f32,
+	pub cost_per_good_die: f32,
+}
+
+impl Shuttle {
+	pub fn layout(&self, field: (f32, f32), scribe: (f32, f32)) -> Vec<Placement> {
+		let mut instances: Vec<usize> = self
+			.designs
+			.iter()
+			.enumerate()
+			.flat_map(|(i, design)| std::iter::repeat(i).take(design.copies as usize))
+			.collect();
+		instances.sort_by(|a, b| self.designs[*b].height.total_cmp(&self.designs[*a].height));
+
+		let mut placements = Vec::with_capacity(instances.len());
+		let (mut x, mut y, mut shelf) = (0.5 * scribe.0, 0.5 * scribe.1, 0.0_f32);
+		for design in instances {
+			let Design { width, height, .. } = self.designs[design];
+			if x + width + 0.5 * scribe.0 > field.0 {
+				x = 0.5 * scribe.0;
+				y += shelf + scribe.1;
+				shelf = 0.0;
+			}
+			if x + width + 0.5 * scribe.0 > field.0 || y + height + 0.5 * scribe.1 > field.1 {
+				continue;
+			}
+
+			placements.push(Placement {
+				design,
+				offset: Coordinate { x, y },
+			});
+			x += width + scribe.0;
+			shelf = shelf.max(height);
+		}
+		placements
+	}
+
+	pub fn results(&self, wafer: &Wafer, sites: &[ShuttleSite], placements: &[Placement]) -> Vec<DesignResult> {
+		let placed: Vec<u32> = (0..self.designs.len())
+			.map(|i| placements.iter().filter(|placement| placement.design == i).count() as u32)
+			.collect();
+		let placed_area: f32 = self
+			.designs
+			.iter()
+			.zip(&placed)
+			.map(|(design, placed)| design.area() * *placed as f32)
+			.sum();
+
+		self.designs
+			.iter()
+			.enumerate()
+			.map(|(i, design)| {
+				let complete = sites
+					.iter()
+					.filter(|site| site.design == i && site.die_type == DieType::Complete)
+					.count();
+
+				let mut design_wafer = wafer.clone();
+				design_wafer.critical_area = design.area();
+				let die_yield = wafer.yield_model.wafer_yield(&design_wafer);
+				let good = complete as f32 * die_yield;
+
+				let area_share = if placed_area > 0.0 {
+					design.area() * placed[i] as f32 / placed_area
+				} else {
+					0.0
+				};
+				let cost = wafer.cost.wafer * area_share + wafer.cost.test * complete as f32;
+
+				DesignResult {
+					placed: placed[i],
+					complete,
+					die_yield,
+					good,
+					area_share,
+					cost_per_good_die: cost / good,
+				}
+			})
+			.collect()
+	}
+}
+
+impl Default for Design {
+	fn default() -> Design {
+		Design {
+			name: String::from("Design"),
+			width: 5.0,
+			height: 5.0,
+			copies: 1,
+		}
+	}
+}
+
+impl Default for Shuttle {
+	fn default() -> Shuttle {
+		Shuttle {
+			designs: vec![
+				Design {
+					name: String::from("Design A"),
+					width: 8.0,
+					height: 6.0,
+					copies: 4,
+				},
+				Design {
+					name: String::from("Design B"),
+					width: 4.0,
+					height: 4.0,
+					copies: 12,
+				},
+			],
+		}
+	}
+}
+
+impl Wafer {
+	pub fn shuttle_sites(&self, placements: &[Placement], shuttle: &Shuttle) -> Vec<ShuttleSite> {
+		let (field_width, field_height) = self.reticle.dimensions();
+		let columns = (self.shape.max_width() / field_width).ceil() as i32;
+		let rows = (self.shape.max_height() / field_height).ceil() as i32;
+		let origin = Coordinate {
+			x: (self.shape.max_width() - columns as f32 * field_width) / 2.0 + self.translation.0,
+			y: (self.shape.max_height() - rows as f32 * field_height) / 2.0 + self.translation.1,
+		};
+
+		let mut sites = Vec::new();
+		for column in -1..=columns {
+			for row in -1..=rows {
+				for placement in placements {
+					let design = &shuttle.designs[placement.design];
+					let coord = Coordinate {
+						x: origin.x + column as f32 * field_width + placement.offset.x,
+						y: origin.y + row as f32 * field_height + placement.offset.y,
+					};
+					let die_type = self.site_type(coord, design.width, design.height);
+					if die_type != DieType::None {
+						sites.push(ShuttleSite {
+							design: placement.design,
+							die_type,
+							coord,
+						});
+					}
+				}
+			}
+		}
+		sites
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn shuttle() -> Shuttle {
+		Shuttle {
+			designs: vec![
+				Design {
+					name: String::from("B"),
+					width: 4.0,
+					height: 4.0,
+					copies: 5,
+				},
+				Design {
+					name: String::from("A"),
+					width: 8.0,
+					height: 6.0,
+					copies: 4,
+				},
+			],
+		}
+	}
+
+	#[test]
+	fn packs_shelves_by_height() {
+		let placements = shuttle().layout((20.0, 20.0), (1.0, 1.0));
+		let layout: Vec<(usize, f32, f32)> = placements
+			.iter()
+			.map(|placement| (placement.design, placement.offset.x, placement.offset.y))
+			.collect();
+		assert_eq!(
+			layout,
+			[
+				(1, 0.5, 0.5),
+				(1, 9.5, 0.5),
+				(1, 0.5, 7.5),
+				(1, 9.5, 7.5),
+				(0, 0.5, 14.5),
+				(0, 5.5, 14.5),
+				(0, 10.5, 14.5),
+				(0, 15.5, 14.5),
+			]
+		);
+	}
+
+	#[test]
+	fn shares_wafer_cost_by_placed_area() {
+		let shuttle = shuttle();
+		let wafer = Wafer::default();
+		let placements = shuttle.layout(wafer.reticle.dimensions(), wafer.scribe_lanes);
+		let sites = wafer.shuttle_sites(&placements, &shuttle);
+		let results = shuttle.results(&wafer, &sites, &placements);
+
+		let placed_area: f32 = results
+			.iter()
+			.zip(&shuttle.designs)
+			.map(|(result, design)| result.placed as f32 * design.area())
+			.sum();
+		for (i, (result, design)) in results.iter().zip(&shuttle.designs).enumerate() {
+			assert_eq!(result.placed, design.copies);
+			let complete = sites
+				.iter()
+				.filter(|site| site.design == i && site.die_type == DieType::Complete)
+				.count();
+			assert_eq!(result.complete, complete);
+			assert!((result.area_share - result.placed as f32 * design.area() / placed_area).abs() < 1e-6);
+			assert!((result.good - complete as f32 * result.die_yield).abs() < 1e-3);
+		}
+		assert!(results[0].die_yield > results[1].die_yield);
+		assert!((results.iter().map(|result| result.area_share).sum::<f32>() - 1.0).abs() < 1e-6);
+	}
+}
@@ -0,0 +1,96 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+use std::fmt;
+use std::fmt::{Display, Formatter};
+
+use serde::{Deserialize, Serialize};
+
+pub const MAXIMUM_STAGGER: f32 = 0.95;
+
+#[derive(Clone, Debug, Default, PartialEq, Deserialize, Serialize)]
+pub enum Stagger {
+	#[default]
+	None,
+	Alternating(f32),
+	Progressive(f32),
+	PerRow(Vec<f32>),
+}
+
+impl Stagger {
+	pub fn fraction(&self) -> f32 {
+		match self {
+			Stagger::None => 0.0,
+			Stagger::Alternating(fraction) | Stagger::Progressive(fraction) => *fraction,
+			Stagger::PerRow(offsets) => offsets.iter().copied().fold(0.0, f32::max),
+		}
+	}
+
+	pub fn is_staggered(&self) -> bool {
+		self.fraction() > 0.0
+	}
+
+	pub fn shift(&self, row: i32) -> f32 {
+		match self {
+			Stagger::None => 0.0,
+			Stagger::Alternating(fraction) => {
+				if row.rem_euclid(2) == 1 {
+					*fraction
+				} else {
+					0.0
+				}
+			}
+			Stagger::Progressive(fraction) => (row as f32 * fraction).rem_euclid(1.0),
+			Stagger::PerRow(offsets) => {
+				if offsets.is_empty() {
+					0.0
+				} else {
+					offsets[row.rem_euclid(offsets.len() as i32) as usize]
+				}
+			}
+		}
+	}
+}
+
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub enum StaggerOption {
+	#[default]
+	None,
+	Alternating,
+	Progressive,
+	PerRow,
+}
+
+impl StaggerOption {
+	pub const ALL: &'static [StaggerOption] = &[
+		StaggerOption::None,
+		StaggerOption::Alternating,
+		StaggerOption::Progressive,
+		StaggerOption::PerRow,
+	];
+}
+
+impl From<&Stagger> for StaggerOption {
+	fn from(stagger: &Stagger) -> StaggerOption {
+		match stagger {
+			Stagger::None => StaggerOption::None,
+			Stagger::Alternating(_) => StaggerOption::Alternating,
+			Stagger::Progressive(_) => StaggerOption::Progressive,
+			Stagger::PerRow(_) => StaggerOption::PerRow,
+		}
+	}
+}
+
+impl Display for StaggerOption {
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		match self {
+			StaggerOption::None => f.write_str("Aligned Rows"),
+			StaggerOption::Alternating => f.write_str("Alternating Rows"),
+			StaggerOption::Progressive => f.write_str("Progressive Rows"),
+			StaggerOption::PerRow => f.write_str("Per-Row Offsets"),
+		}
+	}
+}
@@ -0,0 +1,139 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/.
+ */
+
+use serde::{Deserialize, Serialize};
+
+use crate::die::Die;
+use crate::wafer::Reticle;
+
+pub const MAXIMUM_STITCH_KEEP_OUT: f32 = 5.0;
+
+#[derive(Clone, Copy, Debug, PartialEq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct Stitching {
+	pub enabled: bool,
+	pub yield_loss: f32,
+	pub keep_out: f32,
+}
+
+impl Default for Stitching {
+	fn default() -> Stitching {
+		Stitching {
+			enabled: false,
+			yield_loss: 1.0,
+			keep_out: 0.1,
+		}
+	}
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Exposures {
+	pub columns: u32,
+	pub rows: u32,
+	pub field: (f32, f32),
+}
+
+impl Exposures {
+	pub fn new(die: Die, reticle: Reticle) -> Exposures {
+		let (width, height) = reticle.dimensions();
+		let count = |field_width: f32, field_height: f32| {
+			(
+				(die.width() / field_width).ceil().max(1.0) as u32,
+				(die.height() / field_height).ceil().max(1.0) as u32,
+			)
+		};
+
+		let (columns, rows) = count(width, height);
+		let (rotated_columns, rotated_rows) = count(height, width);
+		if rotated_columns * rotated_rows < columns * rows {
+			Exposures {
+				columns: rotated_columns,
+				rows: rotated_rows,
+				field: (height, width),
+			}
+		} else {
+			Exposures {
+				columns,
+				rows,
+				field: (width, height),
+			}
+		}
+	}
+
+	pub fn is_stitched(&self) -> bool {
+		self.columns > 1 || self.rows > 1
+	}
+
+	pub fn stitches(&self) -> u32 {
+		(self.columns - 1) * self.rows + self.columns * (self.rows - 1)
+	}
+
+	pub fn stitch_lines(&self) -> (Vec<f32>, Vec<f32>) {
+		(
+			(1..self.columns).map(|c| c as f32 * self.field.0).collect(),
+			(1..self.rows).map(|r| r as f32 * self.field.1).collect(),
+		)
+	}
+
+	pub fn keep_out_area(&self, die: Die, keep_out: f32) -> f32 {
+		let band = 2.0 * keep_out;
+		let (width, height) = (die.active_width(), die.active_height());
+		let vertical = ((self.columns - 1) as f32 * band).min(width);
+		let horizontal = ((self.rows - 1) as f32 * band).min(height);
+		vertical * height + horizontal * width - vertical * horizontal
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn exposures(die: (f32, f32), reticle: (f32, f32)) -> Exposures {
+		let die = Die::default().new_width(die.0).new_height(die.1);
+		Exposures::new(
+			die,
+			Reticle::Custom {
+				width: reticle.0,
+				height: reticle.1,
+			},
+		)
+	}
+
+	#[test]
+	fn counts_exposures() {
+		let single = exposures((10.0, 10.0), (26.0, 33.0));
+		assert_eq!((single.columns, single.rows), (1, 1));
+		assert!(!single.is_stitched());
+		assert_eq!(single.stitches(), 0);
+
+		let pair = exposures((30.0, 10.0), (20.0, 20.0));
+		assert_eq!((pair.columns, pair.rows, pair.field), (2, 1, (20.0, 20.0)));
+		assert!(pair.is_stitched());
+		assert_eq!(pair.stitches(), 1);
+
+		let grid = exposures((50.0, 50.0), (20.0, 20.0));
+		assert_eq!((grid.columns, grid.rows), (3, 3));
+		assert_eq!(grid.stitches(), 12);
+		assert_eq!(grid.stitch_lines(), (vec![20.0, 40.0], vec![20.0, 40.0]));
+	}
+
+	#[test]
+	fn rotates_the_field_to_save_exposures() {
+		let rotated = exposures((30.0, 20.0), (26.0, 33.0));
+		assert_eq!((rotated.columns, rotated.rows, rotated.field), (1, 1, (33.0, 26.0)));
+	}
+
+	#[test]
+	fn keeps_out_around_stitches() {
+		let die = Die::default().new_width(30.0).new_height(10.0);
+		assert_eq!(exposures((30.0, 10.0), (20.0, 20.0)).keep_out_area(die, 0.5), 10.0);
+
+		let die = Die::default().new_width(50.0).new_height(50.0);
+		let grid = exposures((50.0, 50.0), (20.0, 20.0));
+		assert_eq!(grid.keep_out_area(die, 0.5), 2.0 * 50.0 + 2.0 * 50.0 - 4.0);
+		assert_eq!(grid.keep_out_area(die, 100.0), die.active_area());
+	}
+}
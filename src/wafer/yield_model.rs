@@ -6,9 +6,11 @@
 
 use std::fmt::{Display, Formatter};
 
+use serde::{Deserialize, Serialize};
+
 use crate::wafer::Wafer;
 
-#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
 pub enum YieldModel {
 	Poisson,
 	#[default]
@@ -16,6 +18,7 @@ pub enum YieldModel {
 	Rectangular,
 	Moore,
 	Seeds,
+	NegativeBinomial,
 }
 
 impl YieldModel {
@@ -25,10 +28,14 @@ impl YieldModel {
 		YieldModel::Rectangular,
 		YieldModel::Moore,
 		YieldModel::Seeds,
+		YieldModel::NegativeBinomial,
 	];
 
 	pub fn wafer_yield(self, wafer: &Wafer) -> f32 {
-		let defects = wafer.critical_area * wafer.defect_rate / 100.0;
+		self.defect_yield(wafer.critical_area * wafer.defect_rate / 100.0, wafer.cluster)
+	}
+
+	pub fn defect_yield(self, defects: f32, cluster: f32) -> f32 {
 		if defects == 0.0 {
 			return 1.0;
 		}
@@ -38,6 +45,7 @@ impl YieldModel {
 			YieldModel::Rectangular => (1.0 - (-2.0 * defects).exp()) / (2.0 * defects),
 			YieldModel::Moore => (-defects.sqrt()).exp(),
 			YieldModel::Seeds => 1.0 / (1.0 + defects),
+			YieldModel::NegativeBinomial => (1.0 + defects / cluster).powf(-cluster),
 		}
 	}
 }
@@ -50,6 +58,7 @@ impl Display for YieldModel {
 			YieldModel::Rectangular => f.write_str("Rectangular Model"),
 			YieldModel::Moore => f.write_str("Moore's Model"),
 			YieldModel::Seeds => f.write_str("Seeds Model"),
+			YieldModel::NegativeBinomial => f.write_str("Negative Binomial Model"),
 		}
 	}
 }